@@ -0,0 +1,284 @@
+use crate::parse::{input_lines, Line, ParseError};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell as `(row, column)`.
+pub type Pos = (usize, usize);
+
+// Offsets are listed in row-major order
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid stored row by row in a single vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character of `input`, which must hold at least
+    /// one line. `cell` returns `None` for characters it rejects.
+    pub fn parse_chars(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let grid = Grid::parse_lines(input_lines(input), expected, cell)?;
+        if grid.height == 0 {
+            return Err(ParseError::end_of_input(input, expected));
+        }
+        Ok(grid)
+    }
+
+    /// Same as `parse_chars`, for a grid that is only one section of the
+    /// input. Every line must be as wide as the first one; no lines at all
+    /// give an empty grid.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = Line<'a>>,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in lines {
+            let mut line_width = 0;
+            for (i, c) in line.text.char_indices() {
+                if Some(line_width) == width {
+                    return Err(line.char_error(i, "end of line"));
+                }
+                cells.push(cell(c).ok_or_else(|| line.char_error(i, expected))?);
+                line_width += 1;
+            }
+            if line_width == 0 {
+                return Err(line.end_error(expected));
+            }
+            let width = *width.get_or_insert(line_width);
+            if line_width < width {
+                return Err(line.end_error(format!("{} cells", width)));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self[pos])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// Positions of every cell, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offsets(
+        &self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(d_row, d_col)| {
+            let row = row.checked_add_signed(d_row)?;
+            let col = col.checked_add_signed(d_col)?;
+            if row < height && col < width {
+                Some((row, col))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Orthogonally adjacent positions that lie inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS4)
+    }
+
+    /// Orthogonally and diagonally adjacent positions that lie inside the
+    /// grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS8)
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a map made of single decimal digits.
+    pub fn parse_digits(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_chars(input, "a digit", |c| c.to_digit(10))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(col < self.width, "column {} out of bounds", col);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(col < self.width, "column {} out of bounds", col);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u32> {
+        Grid::parse_digits("123\n456\n").unwrap()
+    }
+
+    #[test]
+    fn parses_digits_row_by_row() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<u32>>(), vec![3, 6]);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[test]
+    fn rejects_ragged_and_invalid_rows() {
+        let e = Grid::parse_digits("123\n45\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "3 cells"));
+
+        let e = Grid::parse_digits("123\n4567\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (2, 4, "7"));
+
+        let e = Grid::parse_digits("123\n4x6\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 2, "a digit"));
+
+        assert!(Grid::parse_digits("").is_err());
+
+        let e = Grid::parse_digits("12\n\n34\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 1, "a digit"));
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = sample();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<Pos>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<Pos>>(),
+            vec![(0, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours8((0, 2)).collect::<Vec<Pos>>(),
+            vec![(0, 1), (1, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn displays_rows_on_separate_lines() {
+        let grid = sample().map(|&d| if d % 2 == 0 { '#' } else { '.' });
+        assert_eq!(grid.to_string(), ".#.\n#.#\n");
+        assert_eq!(Grid::from_fn(2, 1, |(_, col)| col).to_string(), "01\n");
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Directory of the crate solving day `day`.
+pub fn crate_dir(day: u32) -> PathBuf {
+    // Day crates sit next to this one in the workspace
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    workspace.join(format!("p{}", day))
+}
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interprets a command line argument, `-` meaning stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The `input` file bundled with the crate of day `day`.
+    pub fn bundled(day: u32) -> Self {
+        InputSource::File(crate_dir(day).join("input"))
+    }
+
+    /// Name used to refer to the source in messages.
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::File(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => std::fs::read_to_string(path),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+mod grid;
+mod input;
+mod output;
+mod params;
+mod parse;
+mod rng;
+mod run;
+mod solution;
+mod verify;
+
+pub use grid::{Grid, Pos};
+pub use input::InputSource;
+pub use output::{to_json, Format};
+pub use params::{params_usage, Param, Params};
+pub use parse::{input_lines, Line, ParseError};
+pub use rng::Rng;
+pub use run::{default_seed, execute, main, Day, PartReport, Report};
+pub use solution::{Answer, Diagnostic, Solution};
+pub use verify::{verify, Check, ANSWERS_FILE};
@@ -0,0 +1,104 @@
+use crate::run::Report;
+use crate::solution::Answer;
+
+/// How answers are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => json_string(s),
+    }
+}
+
+/// Renders every part of `reports` as a JSON array holding one object per
+/// part. Each report comes with the name of the input it was run on.
+pub fn to_json(reports: &[(String, Report)]) -> String {
+    let objects = reports
+        .iter()
+        .flat_map(|(input, report)| {
+            report.parts.iter().map(move |part| {
+                let diagnostics = part
+                    .diagnostics
+                    .iter()
+                    .map(|d| format!("{}: {}", json_string(&d.name), json_answer(&d.value)))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!(
+                    "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"elapsed_ms\": {:.6}, \"diagnostics\": {{{}}}}}",
+                    report.day,
+                    part.part,
+                    json_string(input),
+                    json_answer(&part.answer),
+                    part.elapsed.as_secs_f64() * 1000.0,
+                    diagnostics
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::PartReport;
+    use crate::solution::Diagnostic;
+    use std::time::Duration;
+
+    #[test]
+    fn renders_one_object_per_part() {
+        let report = Report {
+            day: 13,
+            parse: Duration::from_millis(1),
+            parts: vec![PartReport {
+                part: 2,
+                answer: Answer::from("#.\n\"x\""),
+                elapsed: Duration::from_micros(1500),
+                diagnostics: vec![Diagnostic::new("bits", 12)],
+            }],
+        };
+        assert_eq!(
+            to_json(&[("p13/input".to_string(), report)]),
+            "[\n  {\"day\": 13, \"part\": 2, \"input\": \"p13/input\", \"answer\": \"#.\\n\\\"x\\\"\", \
+             \"elapsed_ms\": 1.500000, \"diagnostics\": {\"bits\": 12}}\n]\n"
+        );
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+}
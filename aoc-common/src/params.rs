@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+/// A numeric option of a day's puzzle, such as a number of steps, given on
+/// the command line as `--<name> <value>`.
+pub struct Param {
+    pub name: &'static str,
+    /// Short description, including the default value(s)
+    pub help: &'static str,
+    /// Names of the values the param takes, which are stored as their
+    /// index; empty for params taking a positive number
+    pub values: &'static [&'static str],
+}
+
+impl Param {
+    /// Reads the value following `--<name>`: one of the param's `values` if
+    /// it has any, a positive number otherwise.
+    pub fn parse(&self, value: Option<&str>) -> Result<usize, String> {
+        if self.values.is_empty() {
+            return Params::parse_value(self.name, value);
+        }
+        value
+            .and_then(|v| self.values.iter().position(|&name| name == v))
+            .ok_or_else(|| format!("--{} expects one of {}", self.name, self.values.join(", ")))
+    }
+}
+
+/// Values given on the command line for some params. Days fall back to
+/// their own defaults for the others.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, usize>,
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: usize) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.values.get(name).copied()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|name| name.as_str())
+    }
+
+    /// The values given for `params`, leaving out those of other days.
+    pub fn only(&self, params: &[Param]) -> Params {
+        let mut result = Params::default();
+        for param in params {
+            if let Some(value) = self.get(param.name) {
+                result.set(param.name, value);
+            }
+        }
+        result
+    }
+
+    /// Reads the value following `--<name>`, which must be a positive
+    /// number.
+    pub fn parse_value(name: &str, value: Option<&str>) -> Result<usize, String> {
+        value
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|&v| v > 0)
+            .ok_or_else(|| format!("--{} expects a positive number", name))
+    }
+}
+
+/// Describes `params` one per line, for usage messages.
+pub fn params_usage(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|p| {
+            let value = if p.values.is_empty() {
+                "N".to_string()
+            } else {
+                p.values.join("|")
+            };
+            format!("  --{} {}  {}\n", p.name, value, p.help)
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Error raised when puzzle input does not have the expected shape.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    /// Offending token; empty when something was missing
+    pub token: String,
+    /// Description of what was expected instead
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        token: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            line,
+            column,
+            token: token.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error for input that ends while `expected` was still to come.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        ParseError::new(input.lines().count() + 1, 1, "", expected)
+    }
+
+    fn found(&self) -> String {
+        if self.token.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("`{}`", self.token)
+        }
+    }
+
+    /// Renders the error the way compilers do, quoting the offending line of
+    /// `source` and underlining the token. `name` identifies the source, e.g.
+    /// its file name.
+    pub fn render(&self, name: &str, source: &str) -> String {
+        let mut result = format!(
+            "error: expected {}, found {}\n",
+            self.expected,
+            self.found()
+        );
+        let gutter = " ".repeat(self.line.to_string().len());
+        result.push_str(&format!(
+            "{}--> {}:{}:{}\n",
+            gutter, name, self.line, self.column
+        ));
+
+        match source.lines().nth(self.line - 1) {
+            Some(text) => {
+                let underline = "^".repeat(std::cmp::max(1, self.token.chars().count()));
+                result.push_str(&format!("{} |\n", gutter));
+                result.push_str(&format!("{} | {}\n", self.line, text));
+                result.push_str(&format!(
+                    "{} | {}{}\n",
+                    gutter,
+                    " ".repeat(self.column - 1),
+                    underline
+                ));
+            }
+            None => result.push_str(&format!("{} = note: input ended here\n", gutter)),
+        }
+
+        result
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line,
+            self.column,
+            self.expected,
+            self.found()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One line of puzzle input. Tokens handed to its methods must be slices of
+/// `text`, which is how their column is recovered when reporting errors.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Iterates over the lines of `input`, numbered from 1.
+pub fn input_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    /// Error pointing at `token`.
+    pub fn error(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column_of(token), token, expected)
+    }
+
+    /// Error for a line that ends while `expected` was still to come.
+    pub fn end_error(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    /// Error pointing at the character starting at byte `index`.
+    pub fn char_error(&self, index: usize, expected: impl Into<String>) -> ParseError {
+        let len = self.text[index..].chars().next().map_or(0, char::len_utf8);
+        self.error(&self.text[index..index + len], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse::<T>().map_err(|_| self.error(token, expected))
+    }
+
+    /// Takes the next token, failing if the line has run out of them.
+    pub fn next(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        tokens.next().ok_or_else(|| self.end_error(expected))
+    }
+
+    pub fn parse_next<T: FromStr>(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> Result<T, ParseError> {
+        let token = self.next(tokens, expected)?;
+        self.parse(token, expected)
+    }
+
+    /// Takes the next token and checks that it is exactly `word`.
+    pub fn literal(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        word: &str,
+    ) -> Result<(), ParseError> {
+        let expected = format!("`{}`", word);
+        let token = self.next(tokens, &expected)?;
+        if token != word {
+            return Err(self.error(token, expected));
+        }
+        Ok(())
+    }
+
+    /// Checks that no tokens are left on the line.
+    pub fn finish(&self, tokens: &mut impl Iterator<Item = &'a str>) -> Result<(), ParseError> {
+        match tokens.next() {
+            Some(token) => Err(self.error(token, "end of line")),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the line is empty, as between sections of the input.
+    pub fn blank(&self) -> Result<(), ParseError> {
+        if self.text.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(self.text, "an empty line"))
+        }
+    }
+
+    /// Checks that every character of the line satisfies `valid`.
+    pub fn check_chars(
+        &self,
+        valid: impl Fn(char) -> bool,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        match self.text.char_indices().find(|&(_, c)| !valid(c)) {
+            Some((i, _)) => Err(self.char_error(i, expected)),
+            None => Ok(self.text),
+        }
+    }
+
+    /// Parses a line made of single decimal digits.
+    pub fn digits(&self) -> Result<Vec<u32>, ParseError> {
+        self.text
+            .char_indices()
+            .map(|(i, c)| c.to_digit(10).ok_or_else(|| self.char_error(i, "a digit")))
+            .collect()
+    }
+}
@@ -0,0 +1,92 @@
+use std::ops::RangeInclusive;
+
+/// Small seedable pseudo-random number generator (xorshift64*), good enough
+/// for generating puzzle inputs and reproducible from its seed alone.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed with splitmix64 so that close seeds give
+        // unrelated sequences; xorshift needs a non-zero state
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Rng {
+            state: if z == 0 { 1 } else { z },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform number in `range`.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+        let span = end.wrapping_sub(start) as u64;
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+        start.wrapping_add((self.next_u64() % (span + 1)) as i64)
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        // Fisher-Yates
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let first = (0..8).map(|_| a.next_u64()).collect::<Vec<u64>>();
+        assert_eq!(first, (0..8).map(|_| b.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(first, (0..8).map(|_| c.next_u64()).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn stays_within_bounds() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert!(rng.below(7) < 7);
+        }
+        assert_eq!(rng.range(5..=5), 5);
+
+        let mut items = (0..20).collect::<Vec<u32>>();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::input::InputSource;
+use crate::output::{to_json, Format};
+use crate::params::{params_usage, Param, Params};
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use crate::solution::{Answer, Diagnostic, Solution};
+use std::process::exit;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    /// Only filled in when asked for, as computing them takes extra time
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+pub struct Report {
+    pub day: u32,
+    pub parse: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parses `input` once and runs the requested parts on it, timing each step.
+/// With `diagnose`, each part's diagnostics are collected too.
+pub fn execute<S: Solution>(
+    input: &str,
+    parts: &[u8],
+    params: &Params,
+    diagnose: bool,
+) -> Result<Report, ParseError> {
+    let (parsed, parse) = timed(|| S::parse_with(input, params));
+    let parsed = parsed?;
+    let mut report = Report {
+        day: S::DAY,
+        parse,
+        parts: Vec::with_capacity(parts.len()),
+    };
+
+    for &part in parts {
+        let (answer, elapsed) = match part {
+            1 => timed(|| S::part1(&parsed).into()),
+            2 => timed(|| S::part2(&parsed).into()),
+            _ => panic!("Invalid part: {}", part),
+        };
+        let diagnostics = if diagnose {
+            S::diagnostics(&parsed, part)
+        } else {
+            Vec::new()
+        };
+        report.parts.push(PartReport {
+            part,
+            answer,
+            elapsed,
+            diagnostics,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Type-erased handle on a day's `Solution`, so that the runner can keep
+/// every day in a single table.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub params: &'static [Param],
+    pub run: fn(&str, &[u8], &Params, bool) -> Result<Report, ParseError>,
+    pub generate_size: usize,
+    pub generate: fn(&mut Rng, usize, &Params) -> String,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            params: S::PARAMS,
+            run: execute::<S>,
+            generate_size: S::GENERATE_SIZE,
+            generate: S::generate,
+        }
+    }
+}
+
+fn usage_error(usage: &str, message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", usage);
+    exit(1);
+}
+
+fn print_answers(report: Report) {
+    for part in report.parts {
+        match part.answer {
+            Answer::Text(s) if s.contains('\n') => {
+                println!("Part {} - Result:", part.part);
+                print!("{}", s);
+            }
+            answer => println!("Part {} - Result: {}", part.part, answer),
+        }
+    }
+}
+
+fn usage<S: Solution>() -> String {
+    let mut usage = format!(
+        "Usage: p{0} [--input <path>]... [--format text|json]{1}
+       p{0} generate [--size N] [--seed N]{1}",
+        S::DAY,
+        if S::PARAMS.is_empty() {
+            ""
+        } else {
+            " [<option> N]..."
+        }
+    );
+    if !S::PARAMS.is_empty() {
+        usage.push_str("\nOptions:\n");
+        usage.push_str(params_usage(S::PARAMS).trim_end());
+    }
+    usage
+}
+
+// Reads `option` into `params` if it names one of the day's params
+fn read_param<S: Solution>(
+    option: &str,
+    args: &mut impl Iterator<Item = String>,
+    params: &mut Params,
+) -> bool {
+    let param = match option.strip_prefix("--") {
+        Some(name) => match S::PARAMS.iter().find(|p| p.name == name) {
+            Some(param) => param,
+            None => return false,
+        },
+        None => return false,
+    };
+    match param.parse(args.next().as_deref()) {
+        Ok(value) => params.set(param.name, value),
+        Err(e) => usage_error(&usage::<S>(), &e),
+    }
+    true
+}
+
+/// Seed for when none is given, different on every run.
+pub fn default_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn generate_main<S: Solution>(mut args: impl Iterator<Item = String>) {
+    let mut size = S::GENERATE_SIZE;
+    let mut seed = None;
+    let mut params = Params::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => match Params::parse_value("size", args.next().as_deref()) {
+                Ok(value) => size = value,
+                Err(e) => usage_error(&usage::<S>(), &e),
+            },
+            "--seed" => match args.next().and_then(|s| s.parse::<u64>().ok()) {
+                Some(value) => seed = Some(value),
+                None => usage_error(&usage::<S>(), "--seed expects a number"),
+            },
+            option if read_param::<S>(option, &mut args, &mut params) => {}
+            _ => usage_error(&usage::<S>(), &format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let seed = seed.unwrap_or_else(|| {
+        let seed = default_seed();
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    print!("{}", S::generate(&mut Rng::new(seed), size, &params));
+}
+
+/// Entry point shared by every day's binary: solves each input given with
+/// `--input <path>` (`-` for stdin), or else the crate's bundled `input`
+/// file, and prints both answers. `generate` prints a random input instead.
+pub fn main<S: Solution>() {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(|arg| arg.as_str()) == Some("generate") {
+        args.next();
+        generate_main::<S>(args);
+        return;
+    }
+
+    let mut sources = Vec::new();
+    let mut format = Format::Text;
+    let mut params = Params::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => match args.next() {
+                Some(path) => sources.push(InputSource::from_arg(&path)),
+                None => usage_error(&usage::<S>(), "--input expects a path"),
+            },
+            "--format" => match args.next().as_deref().and_then(Format::from_arg) {
+                Some(f) => format = f,
+                None => usage_error(&usage::<S>(), "--format expects text or json"),
+            },
+            "-h" | "--help" => {
+                println!("{}", usage::<S>());
+                return;
+            }
+            option if read_param::<S>(option, &mut args, &mut params) => {}
+            _ => usage_error(&usage::<S>(), &format!("unexpected argument '{}'", arg)),
+        }
+    }
+    if sources.is_empty() {
+        sources.push(InputSource::bundled(S::DAY));
+    }
+
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for source in &sources {
+        if sources.len() > 1 && format == Format::Text {
+            println!("{}:", source.name());
+        }
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("error: cannot read {}: {}", source.name(), e);
+                failed = true;
+                continue;
+            }
+        };
+        match execute::<S>(&input, &[1, 2], &params, format == Format::Json) {
+            Ok(report) if format == Format::Text => print_answers(report),
+            Ok(report) => reports.push((source.name(), report)),
+            Err(e) => {
+                eprint!("{}", e.render(&source.name(), &input));
+                failed = true;
+            }
+        }
+    }
+    if format == Format::Json {
+        print!("{}", to_json(&reports));
+    }
+    if failed {
+        exit(1);
+    }
+}
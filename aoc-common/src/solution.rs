@@ -0,0 +1,117 @@
+use crate::params::{Param, Params};
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use std::fmt::{Display, Formatter};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+/// Values too large for `Answer::Number` are kept as text.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+/// Parts that may not have an answer, e.g. because it overflows, say why
+/// in its place.
+impl<T: Into<Answer>, E: Display> From<Result<T, E>> for Answer {
+    fn from(result: Result<T, E>) -> Self {
+        result.map_or_else(|e| Answer::Text(format!("none ({})", e)), Into::into)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A named value computed on the way to an answer, such as an intermediate
+/// result, reported alongside it in machine-readable output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub name: String,
+    pub value: Answer,
+}
+
+impl Diagnostic {
+    pub fn new(name: &str, value: impl Into<Answer>) -> Self {
+        Diagnostic {
+            name: name.to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A day's puzzle: how to parse its input and how to answer both parts.
+///
+/// Both parts borrow the parsed input, so a part that needs to mutate it
+/// works on its own copy.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    /// Options of the puzzle that can be changed from the command line.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Same as `parse`, with values given for some of `PARAMS`. Days that
+    /// have params keep them in their `Input` for the parts to use.
+    fn parse_with(input: &str, _params: &Params) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Diagnostics for `part`, computed apart from the timed run. Most days
+    /// have none.
+    fn diagnostics(_input: &Self::Input, _part: u8) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+
+    /// Size `generate` is asked for when none is given.
+    const GENERATE_SIZE: usize = 100;
+
+    /// A random, valid input for stress tests and benchmarks. What `size`
+    /// counts, e.g. lines or the side of a grid, depends on the day.
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String;
+}
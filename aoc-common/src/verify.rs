@@ -0,0 +1,128 @@
+use crate::input::crate_dir;
+use crate::params::Params;
+use crate::run::Day;
+use crate::solution::Answer;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Name of the file, next to a day's `input`, listing its expected answers.
+///
+/// Each table is named after an input file of the crate and gives the
+/// answers known for it, so a sample may only list one part:
+///
+/// ```toml
+/// [input]
+/// part1 = 1446
+/// part2 = 1486
+///
+/// [sample]
+/// part1 = 7
+/// ```
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Result of comparing one part's answer against the expected one.
+pub struct Check {
+    pub day: u32,
+    /// Input file the part was run on, relative to the day's crate
+    pub file: String,
+    pub part: u8,
+    pub expected: Answer,
+    /// Answer produced, or why none was
+    pub actual: Result<Answer, String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        // Compare the printed forms, as TOML has no room for i128 numbers
+        match &self.actual {
+            Ok(actual) => actual.to_string() == self.expected.to_string(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} {} part {}: ", self.day, self.file, self.part)?;
+        match &self.actual {
+            Ok(actual) if self.passed() => write!(f, "{}", actual),
+            Ok(actual) => write!(f, "expected {}, got {}", self.expected, actual),
+            Err(e) => write!(f, "expected {}, {}", self.expected, e),
+        }
+    }
+}
+
+fn expected_answer(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(n) => Some(Answer::from(*n)),
+        toml::Value::String(s) => Some(Answer::from(s.as_str())),
+        _ => None,
+    }
+}
+
+/// Runs `day` on every input listed in its answers file and checks each
+/// part against the expected answer. Fails if the answers file itself is
+/// missing or malformed.
+pub fn verify(day: &Day) -> Result<Vec<Check>, String> {
+    let dir = crate_dir(day.number);
+    let answers_path = dir.join(ANSWERS_FILE);
+    let answers = std::fs::read_to_string(&answers_path)
+        .map_err(|e| format!("cannot read {}: {}", answers_path.display(), e))?
+        .parse::<toml::Table>()
+        .map_err(|e| format!("invalid {}: {}", answers_path.display(), e))?;
+
+    let mut checks = Vec::new();
+    for (file, table) in &answers {
+        let mut expected = Vec::new();
+        for part in [1, 2] {
+            let key = format!("part{}", part);
+            if let Some(value) = table.get(&key) {
+                let answer = expected_answer(value).ok_or_else(|| {
+                    format!(
+                        "invalid {}: {}.{} must be an integer or a string",
+                        answers_path.display(),
+                        file,
+                        key
+                    )
+                })?;
+                expected.push((part, answer));
+            }
+        }
+
+        let path: PathBuf = dir.join(file);
+        let parts = expected.iter().map(|(part, _)| *part).collect::<Vec<u8>>();
+        let actual = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))
+            .and_then(|input| {
+                (day.run)(&input, &parts, &Params::default(), false)
+                    .map_err(|e| format!("cannot parse {}: {}", file, e))
+            });
+
+        match actual {
+            Ok(report) => {
+                for ((part, expected), part_report) in expected.into_iter().zip(report.parts) {
+                    checks.push(Check {
+                        day: day.number,
+                        file: file.clone(),
+                        part,
+                        expected,
+                        actual: Ok(part_report.answer),
+                    });
+                }
+            }
+            Err(e) => {
+                for (part, expected) in expected {
+                    checks.push(Check {
+                        day: day.number,
+                        file: file.clone(),
+                        part,
+                        expected,
+                        actual: Err(e.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(checks)
+}
@@ -0,0 +1,138 @@
+use aoc_common::{Day, Params, ParseError};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn of(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of one step of a day: parsing its input or solving one part.
+pub struct Measurement {
+    pub day: u32,
+    pub step: String,
+    pub iterations: usize,
+    pub stats: Stats,
+    /// Values of the day's params the step ran with
+    pub params: Params,
+}
+
+/// Runs `day` `warmup` times without measuring, then `iterations` times
+/// recording how long parsing and each of `parts` take.
+pub fn bench(
+    day: &Day,
+    input: &str,
+    parts: &[u8],
+    params: &Params,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    assert!(iterations > 0);
+    for _ in 0..warmup {
+        (day.run)(input, parts, params, false)?;
+    }
+
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut part_times = vec![Vec::with_capacity(iterations); parts.len()];
+    for _ in 0..iterations {
+        let report = (day.run)(input, parts, params, false)?;
+        parse_times.push(report.parse);
+        for (times, part) in part_times.iter_mut().zip(report.parts) {
+            times.push(part.elapsed);
+        }
+    }
+
+    let steps = std::iter::once(("parse".to_string(), parse_times)).chain(
+        parts
+            .iter()
+            .zip(part_times)
+            .map(|(part, times)| (format!("part{}", part), times)),
+    );
+    Ok(steps
+        .map(|(step, times)| Measurement {
+            day: day.number,
+            step,
+            iterations,
+            stats: Stats::of(times),
+            params: params.only(day.params),
+        })
+        .collect())
+}
+
+/// Appends `measurements` to the CSV history at `path`, writing the header
+/// first if the file is new.
+pub fn append_csv(path: &Path, measurements: &[Measurement]) -> std::io::Result<()> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(
+            file,
+            "timestamp,day,step,iterations,min_ms,median_ms,max_ms,params"
+        )?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    for m in measurements {
+        let params = m
+            .params
+            .names()
+            .map(|name| format!("{}={}", name, m.params.get(name).unwrap()))
+            .collect::<Vec<String>>()
+            .join(";");
+        writeln!(
+            file,
+            "{},{},{},{},{:.6},{:.6},{:.6},{}",
+            timestamp,
+            m.day,
+            m.step,
+            m.iterations,
+            ms(m.stats.min),
+            ms(m.stats.median),
+            ms(m.stats.max),
+            params
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn median_is_the_middle_sample_or_the_mean_of_both() {
+        let ms = Duration::from_millis;
+        let stats = Stats::of(vec![ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(
+            (stats.min, stats.median, stats.max),
+            (ms(1), ms(2) + ms(1) / 2, ms(4))
+        );
+
+        let stats = Stats::of(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(stats.median, ms(3));
+    }
+}
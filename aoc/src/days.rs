@@ -0,0 +1,70 @@
+use aoc_common::Day;
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::of::<p1::Day1>(),
+        Day::of::<p2::Day2>(),
+        Day::of::<p3::Day3>(),
+        Day::of::<p4::Day4>(),
+        Day::of::<p5::Day5>(),
+        Day::of::<p6::Day6>(),
+        Day::of::<p7::Day7>(),
+        Day::of::<p8::Day8>(),
+        Day::of::<p9::Day9>(),
+        Day::of::<p10::Day10>(),
+        Day::of::<p11::Day11>(),
+        Day::of::<p12::Day12>(),
+        Day::of::<p13::Day13>(),
+        Day::of::<p14::Day14>(),
+        Day::of::<p15::Day15>(),
+        Day::of::<p16::Day16>(),
+        Day::of::<p18::Day18>(),
+        Day::of::<p20::Day20>(),
+        Day::of::<p21::Day21>(),
+        Day::of::<p24::Day24>(),
+    ]
+}
+
+pub fn find(number: u32) -> Option<Day> {
+    all().into_iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{Params, Rng};
+
+    #[test]
+    fn every_day_matches_its_expected_answers() {
+        let mut failures = Vec::new();
+        for day in super::all() {
+            match aoc_common::verify(&day) {
+                Ok(checks) => failures.extend(
+                    checks
+                        .into_iter()
+                        .filter(|check| !check.passed())
+                        .map(|check| check.to_string()),
+                ),
+                Err(e) => failures.push(e),
+            }
+        }
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn every_day_solves_its_generated_inputs() {
+        for day in super::all() {
+            for seed in 0..3 {
+                let size = day.generate_size.min(10);
+                let input = (day.generate)(&mut Rng::new(seed), size, &Params::default());
+                let report = (day.run)(&input, &[1, 2], &Params::default(), false);
+                assert!(
+                    report.is_ok(),
+                    "day {} seed {}: {}",
+                    day.number,
+                    seed,
+                    report.err().unwrap()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+mod benchmark;
+mod days;
+
+use aoc_common::{Day, Format, InputSource, Params, Report, Rng};
+use benchmark::Measurement;
+use std::path::PathBuf;
+use std::process::exit;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: aoc run <day> [--part 1|2] [--input <path>]... [--format text|json]
+       aoc run --all [--part 1|2] [--format text|json]
+       aoc verify [<day>...|--all]
+       aoc bench <day>...|--all [--part 1|2] [--warmup N] [--iterations N] [--csv <path>]
+       aoc generate <day> [--size N] [--seed N]
+
+Options of the puzzles themselves, e.g. --steps N, are passed on to the
+selected days that take them (see `cargo run -p <day> -- --help`).";
+
+const DEFAULT_WARMUP: usize = 3;
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_CSV: &str = "bench.csv";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3} ms", d.as_secs_f64() * 1000.0)
+}
+
+fn print_summary(reports: &[(String, Report)]) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>12}",
+        "Day", "Part", "Answer", "Time"
+    );
+    let mut total = Duration::default();
+    for (_, report) in reports {
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>12}",
+            report.day,
+            "-",
+            "(parse)",
+            format_duration(report.parse)
+        );
+        total += report.parse;
+        for part in &report.parts {
+            let answer = part.answer.to_string();
+            let mut lines = answer.lines();
+            println!(
+                "{:>3}  {:>4}  {:<20}  {:>12}",
+                report.day,
+                part.part,
+                lines.next().unwrap_or(""),
+                format_duration(part.elapsed)
+            );
+            // Multi-line answers (e.g. day 13's folded paper) go below the row
+            for line in lines {
+                println!("{:>11}  {}", "", line);
+            }
+            total += part.elapsed;
+        }
+    }
+    println!("Total time: {}", format_duration(total));
+}
+
+fn print_measurements(measurements: &[Measurement]) {
+    println!(
+        "{:>3}  {:<6}  {:>12}  {:>12}  {:>12}",
+        "Day", "Step", "Min", "Median", "Max"
+    );
+    for m in measurements {
+        println!(
+            "{:>3}  {:<6}  {:>12}  {:>12}  {:>12}",
+            m.day,
+            m.step,
+            format_duration(m.stats.min),
+            format_duration(m.stats.median),
+            format_duration(m.stats.max)
+        );
+    }
+}
+
+fn parse_part(arg: Option<&String>) -> u8 {
+    arg.and_then(|p| p.parse::<u8>().ok())
+        .filter(|p| *p == 1 || *p == 2)
+        .unwrap_or_else(|| usage_error("--part expects 1 or 2"))
+}
+
+fn parse_count(option: &str, arg: Option<&String>) -> usize {
+    arg.and_then(|n| n.parse::<usize>().ok())
+        .unwrap_or_else(|| usage_error(&format!("{} expects a number", option)))
+}
+
+fn parse_param(option: &str, value: Option<&String>, params: &mut Params) {
+    let name = &option[2..];
+    let value = value.map(|v| v.as_str());
+    // Options no day takes are numbers until check_params rejects them
+    let value = match days::all()
+        .into_iter()
+        .flat_map(|day| day.params)
+        .find(|p| p.name == name)
+    {
+        Some(param) => param.parse(value),
+        None => Params::parse_value(name, value),
+    }
+    .unwrap_or_else(|e| usage_error(&e));
+    params.set(name, value);
+}
+
+// Every option given must be taken by at least one of the selected days
+fn check_params(params: &Params, days: &[Day]) {
+    for name in params.names() {
+        if !days
+            .iter()
+            .any(|day| day.params.iter().any(|p| p.name == name))
+        {
+            usage_error(&format!("unknown option '--{}'", name));
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Day {
+    arg.trim_start_matches('p')
+        .parse::<u32>()
+        .ok()
+        .and_then(days::find)
+        .unwrap_or_else(|| usage_error(&format!("unknown day '{}'", arg)))
+}
+
+fn run(args: &[String]) {
+    let mut it = args.iter();
+    let mut selected_days = Vec::new();
+    let mut parts = vec![1, 2];
+    let mut sources = Vec::new();
+    let mut format = Format::Text;
+    let mut params = Params::default();
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--all" => selected_days = days::all(),
+            "--part" => parts = vec![parse_part(it.next())],
+            "--format" => {
+                format = it
+                    .next()
+                    .and_then(|f| Format::from_arg(f))
+                    .unwrap_or_else(|| usage_error("--format expects text or json"));
+            }
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                sources.push(InputSource::from_arg(path));
+            }
+            option if option.starts_with("--") => parse_param(option, it.next(), &mut params),
+            day => selected_days.push(parse_day(day)),
+        }
+    }
+
+    if selected_days.is_empty() {
+        usage_error("no day selected");
+    }
+    check_params(&params, &selected_days);
+    if !sources.is_empty() && selected_days.len() != 1 {
+        usage_error("--input needs exactly one day");
+    }
+
+    // Without --input, every day runs on its bundled input file
+    let runs = selected_days
+        .into_iter()
+        .flat_map(|day| {
+            if sources.is_empty() {
+                vec![(day, InputSource::bundled(day.number))]
+            } else {
+                sources.iter().map(|source| (day, source.clone())).collect()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut failed = false;
+    let reports = runs
+        .into_iter()
+        .filter_map(|(day, source)| {
+            let input = source.read().unwrap_or_else(|e| {
+                eprintln!("error: cannot read {}: {}", source.name(), e);
+                exit(1);
+            });
+            match (day.run)(&input, &parts, &params, format == Format::Json) {
+                Ok(report) => Some((source.name(), report)),
+                Err(e) => {
+                    eprint!("{}", e.render(&source.name(), &input));
+                    failed = true;
+                    None
+                }
+            }
+        })
+        .collect::<Vec<(String, Report)>>();
+
+    match format {
+        Format::Text => print_summary(&reports),
+        Format::Json => print!("{}", aoc_common::to_json(&reports)),
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn verify(args: &[String]) {
+    let mut selected_days = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--all" => selected_days = days::all(),
+            day => selected_days.push(parse_day(day)),
+        }
+    }
+    if selected_days.is_empty() {
+        selected_days = days::all();
+    }
+
+    let mut total = 0;
+    let mut failures = 0;
+    for day in selected_days {
+        let checks = aoc_common::verify(&day).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+        for check in checks {
+            total += 1;
+            if check.passed() {
+                println!("ok    {}", check);
+            } else {
+                failures += 1;
+                println!("FAIL  {}", check);
+            }
+        }
+    }
+
+    println!("{} checks, {} failed", total, failures);
+    if failures > 0 {
+        exit(1);
+    }
+}
+
+fn bench(args: &[String]) {
+    let mut it = args.iter();
+    let mut selected_days = Vec::new();
+    let mut parts = vec![1, 2];
+    let mut warmup = DEFAULT_WARMUP;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut csv = PathBuf::from(DEFAULT_CSV);
+    let mut params = Params::default();
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--all" => selected_days = days::all(),
+            "--part" => parts = vec![parse_part(it.next())],
+            "--warmup" => warmup = parse_count(arg, it.next()),
+            "--iterations" => iterations = parse_count(arg, it.next()),
+            "--csv" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--csv expects a path"));
+                csv = PathBuf::from(path);
+            }
+            option if option.starts_with("--") => parse_param(option, it.next(), &mut params),
+            day => selected_days.push(parse_day(day)),
+        }
+    }
+
+    if selected_days.is_empty() {
+        usage_error("no day selected");
+    }
+    check_params(&params, &selected_days);
+    if iterations == 0 {
+        usage_error("--iterations must be at least 1");
+    }
+
+    let mut measurements = Vec::new();
+    for day in selected_days {
+        let source = InputSource::bundled(day.number);
+        let input = source.read().unwrap_or_else(|e| {
+            eprintln!("error: cannot read {}: {}", source.name(), e);
+            exit(1);
+        });
+        match benchmark::bench(&day, &input, &parts, &params, warmup, iterations) {
+            Ok(day_measurements) => measurements.extend(day_measurements),
+            Err(e) => {
+                eprint!("{}", e.render(&source.name(), &input));
+                exit(1);
+            }
+        }
+    }
+
+    print_measurements(&measurements);
+    if let Err(e) = benchmark::append_csv(&csv, &measurements) {
+        eprintln!("error: cannot write {}: {}", csv.display(), e);
+        exit(1);
+    }
+}
+
+fn generate(args: &[String]) {
+    let mut it = args.iter();
+    let mut day = None;
+    let mut size = None;
+    let mut seed = None;
+    let mut params = Params::default();
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--size" => {
+                let value = Params::parse_value("size", it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+                size = Some(value);
+            }
+            "--seed" => {
+                let value = it
+                    .next()
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or_else(|| usage_error("--seed expects a number"));
+                seed = Some(value);
+            }
+            option if option.starts_with("--") => parse_param(option, it.next(), &mut params),
+            arg if day.is_none() => day = Some(parse_day(arg)),
+            _ => usage_error("generate takes a single day"),
+        }
+    }
+
+    let day = day.unwrap_or_else(|| usage_error("no day selected"));
+    check_params(&params, &[day]);
+    let seed = seed.unwrap_or_else(|| {
+        let seed = aoc_common::default_seed();
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let size = size.unwrap_or(day.generate_size);
+    print!("{}", (day.generate)(&mut Rng::new(seed), size, &params));
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some(command) => usage_error(&format!("unknown command '{}'", command)),
+        None => usage_error("missing command"),
+    }
+}
@@ -0,0 +1,124 @@
+use aoc_common::{input_lines, Line, Param, Params, ParseError, Rng, Solution};
+
+pub mod profile;
+pub mod stream;
+
+fn parse_depth(line: &Line) -> Result<i32, ParseError> {
+    line.parse::<i32>(line.text, "a depth")
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    input_lines(input)
+        .map(|line| parse_depth(&line))
+        .collect::<Result<Vec<i32>, ParseError>>()
+}
+
+const WINDOW: usize = 3;
+
+/// Sums of every `window` consecutive depths, in one pass: each step adds the
+/// depth entering the window and subtracts the one leaving it.
+pub fn window_sums(depth_list: &[i32], window: usize) -> impl Iterator<Item = i64> + Clone + '_ {
+    assert!(window > 0, "empty window");
+    depth_list
+        .iter()
+        .enumerate()
+        .scan(0, move |sum, (i, &depth)| {
+            *sum += depth as i64;
+            if i >= window {
+                *sum -= depth_list[i - window] as i64;
+            }
+            Some(*sum)
+        })
+        .skip(window - 1)
+}
+
+/// Counts the sums of `window` consecutive depths that are larger than the
+/// previous one. Inputs with fewer than `window + 1` depths have none.
+pub fn count_increases(depth_list: &[i32], window: usize) -> u32 {
+    let sums = window_sums(depth_list, window);
+    sums.clone()
+        .zip(sums.skip(1))
+        .filter(|(previous, sum)| sum > previous)
+        .count() as u32
+}
+
+pub fn part1(depth_list: &[i32]) -> u32 {
+    count_increases(depth_list, 1)
+}
+
+pub fn part2(depth_list: &[i32], window: usize) -> u32 {
+    count_increases(depth_list, window)
+}
+
+/// A random sonar sweep of `size` depths, drifting deeper like the puzzle's.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    let mut result = String::new();
+    for _ in 0..size {
+        result.push_str(&format!("{}\n", depth));
+        depth = (depth + rng.range(-10..=20)).max(0);
+    }
+    result
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    // Depths, and the size of part 2's window if given
+    type Input = (Vec<i32>, Option<usize>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "window",
+        help: "depths summed together in part 2 (default: 3)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 2000;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((parse_input(input)?, params.get("window")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, input.1.unwrap_or(WINDOW))
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_increases;
+
+    const SAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn counts_increases_of_any_window() {
+        assert_eq!(count_increases(&SAMPLE, 1), 7);
+        assert_eq!(count_increases(&SAMPLE, 3), 5);
+        assert_eq!(count_increases(&SAMPLE, 9), 1);
+        assert_eq!(count_increases(&SAMPLE, 10), 0);
+    }
+
+    #[test]
+    fn short_inputs_have_no_increases() {
+        assert_eq!(count_increases(&[], 1), 0);
+        assert_eq!(count_increases(&[1], 1), 0);
+        assert_eq!(count_increases(&[1, 2], 3), 0);
+        assert_eq!(count_increases(&[1, 2, 3], 3), 0);
+    }
+}
@@ -1,21 +1,152 @@
-use std::io::{self, BufRead};
+use aoc_common::{InputSource, Params};
+use p1::stream::{self, IncreaseCounter};
+use p1::{parse_input, profile};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::process::exit;
+use std::time::Duration;
 
-fn main() {
-    let depth_list = io::stdin()
-        .lock()
-        .lines()
-        .map(|l| l.unwrap().parse::<i32>().unwrap())
-        .collect::<Vec<i32>>();
-
-    let mut result = 0;
-    let mut last_window_sum = i32::max_value();
-    for i in 0..depth_list.len() - 2 {
-        let window_sum = depth_list[i] + depth_list[i + 1] + depth_list[i + 2];
-        if window_sum > last_window_sum {
-            result += 1;
+const USAGE: &str = "Usage: p1 stream [--input <path>] [--window N] [--every N] [--follow]
+       p1 profile [--input <path>] [--window N] [--csv series|histogram]
+
+`stream` counts increases while reading depths line by line, printing the
+running count every N depths (default: 1000). With --follow, the input file
+is tailed until a line holding `EOF` is appended to it.
+
+`profile` describes the depths: longest runs, largest drop, moving average
+over a window of N depths (default: 3) and step deltas. --csv prints the
+moving average series or the histogram of deltas as CSV instead.";
+
+const DEFAULT_WINDOW: usize = 1;
+const DEFAULT_PROFILE_WINDOW: usize = 3;
+const DEFAULT_EVERY: usize = 1000;
+// How long a followed file is left to grow before reading it again
+const FOLLOW_POLL: Duration = Duration::from_millis(200);
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
+}
+
+fn stream_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut source = InputSource::bundled(1);
+    let mut window = DEFAULT_WINDOW;
+    let mut every = DEFAULT_EVERY;
+    let mut follow = false;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                source = InputSource::from_arg(path);
+            }
+            "--window" | "--every" => {
+                let value = Params::parse_value(&arg[2..], it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+                if arg == "--window" {
+                    window = value;
+                } else {
+                    every = value;
+                }
+            }
+            "--follow" => follow = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
         }
-        last_window_sum = window_sum;
     }
 
-    println!("{}", result);
+    let reader: Box<dyn BufRead> = match &source {
+        InputSource::Stdin if follow => usage_error("--follow needs an input file"),
+        InputSource::Stdin => Box::new(std::io::stdin().lock()),
+        InputSource::File(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("error: cannot read {}: {}", source.name(), e);
+                exit(1);
+            }
+        },
+    };
+
+    let mut counter = IncreaseCounter::new(window);
+    let report =
+        |c: &IncreaseCounter| println!("{} depths, {} increases", c.depths(), c.increases());
+    let poll = follow.then_some(FOLLOW_POLL);
+    if let Err(e) = stream::run(reader, &mut counter, every as u64, poll, report) {
+        eprintln!("error: {}: {}", source.name(), e);
+        exit(1);
+    }
+    if !counter.depths().is_multiple_of(every as u64) {
+        report(&counter);
+    }
+}
+
+enum Csv {
+    Series,
+    Histogram,
+}
+
+fn profile_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut source = InputSource::bundled(1);
+    let mut window = DEFAULT_PROFILE_WINDOW;
+    let mut csv = None;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                source = InputSource::from_arg(path);
+            }
+            "--window" => {
+                window = Params::parse_value("window", it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+            }
+            "--csv" => {
+                csv = match it.next().map(|v| v.as_str()) {
+                    Some("series") => Some(Csv::Series),
+                    Some("histogram") => Some(Csv::Histogram),
+                    _ => usage_error("--csv expects series or histogram"),
+                };
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let input = source.read().unwrap_or_else(|e| {
+        eprintln!("error: cannot read {}: {}", source.name(), e);
+        exit(1);
+    });
+    let depths = parse_input(&input).unwrap_or_else(|e| {
+        eprint!("{}", e.render(&source.name(), &input));
+        exit(1);
+    });
+
+    let profile = profile::profile(&depths, window);
+    match csv {
+        Some(Csv::Series) => print!("{}", profile.series_csv()),
+        Some(Csv::Histogram) => print!("{}", profile.histogram_csv()),
+        None => print!("{}", profile),
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("stream") => stream_main(&args[1..]),
+        Some("profile") => profile_main(&args[1..]),
+        _ => aoc_common::main::<p1::Day1>(),
+    }
 }
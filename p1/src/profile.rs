@@ -0,0 +1,205 @@
+use crate::window_sums;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Consecutive steps going the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    /// Index of the depth the run starts from
+    pub start: usize,
+    /// Number of steps, one less than the number of depths
+    pub steps: usize,
+}
+
+/// Statistics describing the shape of a depth list.
+#[derive(Debug)]
+pub struct Profile {
+    pub depths: Vec<i32>,
+    pub longest_increase: Option<Run>,
+    pub longest_decrease: Option<Run>,
+    /// Index of the depth the largest drop starts from, and its size
+    pub max_drop: Option<(usize, i64)>,
+    pub window: usize,
+    /// Average of every `window` consecutive depths, the first one ending on
+    /// depth `window - 1`
+    pub moving_average: Vec<f64>,
+    /// Number of steps by each change in depth
+    pub histogram: BTreeMap<i64, usize>,
+}
+
+// Longest run of steps for which `going` holds, the earliest one on ties
+fn longest_run(depths: &[i32], going: impl Fn(i32, i32) -> bool) -> Option<Run> {
+    let mut longest: Option<Run> = None;
+    let mut current = Run { start: 0, steps: 0 };
+    for (i, step) in depths.windows(2).enumerate() {
+        if going(step[0], step[1]) {
+            if current.steps == 0 {
+                current.start = i;
+            }
+            current.steps += 1;
+            if longest.is_none_or(|run| current.steps > run.steps) {
+                longest = Some(current);
+            }
+        } else {
+            current.steps = 0;
+        }
+    }
+    longest
+}
+
+pub fn profile(depths: &[i32], window: usize) -> Profile {
+    // Steps between two i32 depths may not fit in an i32
+    let max_drop = depths
+        .windows(2)
+        .enumerate()
+        .map(|(i, step)| (i, step[0] as i64 - step[1] as i64))
+        .filter(|&(_, drop)| drop > 0)
+        // Earliest of the largest drops
+        .min_by_key(|&(i, drop)| (-drop, i));
+
+    let mut histogram = BTreeMap::new();
+    for step in depths.windows(2) {
+        *histogram
+            .entry(step[1] as i64 - step[0] as i64)
+            .or_insert(0) += 1;
+    }
+
+    Profile {
+        depths: depths.to_vec(),
+        longest_increase: longest_run(depths, |a, b| b > a),
+        longest_decrease: longest_run(depths, |a, b| b < a),
+        max_drop,
+        window,
+        moving_average: window_sums(depths, window)
+            .map(|sum| sum as f64 / window as f64)
+            .collect(),
+        histogram,
+    }
+}
+
+impl Profile {
+    /// Every depth with the moving average of the window ending on it, left
+    /// empty for the first `window - 1` depths.
+    pub fn series_csv(&self) -> String {
+        let mut result = "line,depth,moving_average\n".to_string();
+        for (i, depth) in self.depths.iter().enumerate() {
+            let average = (i + 1)
+                .checked_sub(self.window)
+                .map_or(String::new(), |j| format!("{:.3}", self.moving_average[j]));
+            result.push_str(&format!("{},{},{}\n", i + 1, depth, average));
+        }
+        result
+    }
+
+    pub fn histogram_csv(&self) -> String {
+        let mut result = "delta,count\n".to_string();
+        for (delta, count) in &self.histogram {
+            result.push_str(&format!("{},{}\n", delta, count));
+        }
+        result
+    }
+
+    fn describe_run(
+        &self,
+        f: &mut Formatter<'_>,
+        name: &str,
+        run: Option<Run>,
+    ) -> std::fmt::Result {
+        match run {
+            Some(run) => writeln!(
+                f,
+                "longest {} run: {} steps, lines {} to {} ({} to {})",
+                name,
+                run.steps,
+                run.start + 1,
+                run.start + run.steps + 1,
+                self.depths[run.start],
+                self.depths[run.start + run.steps]
+            ),
+            None => writeln!(f, "longest {} run: none", name),
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "depths: {}", self.depths.len())?;
+        self.describe_run(f, "increasing", self.longest_increase)?;
+        self.describe_run(f, "decreasing", self.longest_decrease)?;
+        match self.max_drop {
+            Some((i, drop)) => writeln!(
+                f,
+                "largest drop: {}, lines {} to {} ({} to {})",
+                drop,
+                i + 1,
+                i + 2,
+                self.depths[i],
+                self.depths[i + 1]
+            )?,
+            None => writeln!(f, "largest drop: none")?,
+        }
+
+        let averages = &self.moving_average;
+        if averages.is_empty() {
+            writeln!(f, "moving average over {}: none", self.window)?;
+        } else {
+            let min = averages.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = averages.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            writeln!(
+                f,
+                "moving average over {}: {} values from {:.3} to {:.3}",
+                self.window,
+                averages.len(),
+                min,
+                max
+            )?;
+        }
+
+        writeln!(f, "step deltas:")?;
+        for (delta, count) in &self.histogram {
+            writeln!(f, "{:>6}  {}", delta, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn profiles_the_sample() {
+        let profile = profile(&SAMPLE, 3);
+        assert_eq!(profile.longest_increase, Some(Run { start: 0, steps: 3 }));
+        assert_eq!(profile.longest_decrease, Some(Run { start: 3, steps: 1 }));
+        assert_eq!(profile.max_drop, Some((3, 10)));
+        assert_eq!(profile.moving_average.len(), 8);
+        assert_eq!(profile.moving_average[0], 607.0 / 3.0);
+        assert_eq!(profile.histogram.values().sum::<usize>(), 9);
+        assert_eq!(profile.histogram[&-10], 1);
+        assert!(profile
+            .series_csv()
+            .starts_with("line,depth,moving_average\n1,199,\n2,200,\n3,208,202.333\n"));
+    }
+
+    #[test]
+    fn profiles_short_inputs() {
+        let profile = profile(&[5], 2);
+        assert_eq!(profile.longest_increase, None);
+        assert_eq!(profile.max_drop, None);
+        assert!(profile.moving_average.is_empty());
+        assert_eq!(profile.histogram_csv(), "delta,count\n");
+    }
+
+    #[test]
+    fn profiles_steps_wider_than_an_i32() {
+        let profile = profile(&[2_000_000_000, -2_000_000_000, 0], 2);
+        assert_eq!(profile.max_drop, Some((0, 4_000_000_000)));
+        assert_eq!(
+            profile.histogram_csv(),
+            "delta,count\n-4000000000,1\n2000000000,1\n"
+        );
+    }
+}
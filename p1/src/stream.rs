@@ -0,0 +1,185 @@
+use crate::parse_depth;
+use aoc_common::{Line, ParseError};
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::time::Duration;
+
+/// Line appended to a followed file to signal that no more depths will come.
+pub const END_MARKER: &str = "EOF";
+
+/// Counts the increases of sliding-window sums over depths fed one at a
+/// time, keeping only the last `window` depths in a ring buffer.
+pub struct IncreaseCounter {
+    ring: Vec<i32>,
+    // Position of the oldest depth, once the ring is full
+    oldest: usize,
+    window: usize,
+    depths: u64,
+    increases: u64,
+}
+
+impl IncreaseCounter {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "empty window");
+        IncreaseCounter {
+            ring: Vec::with_capacity(window),
+            oldest: 0,
+            window,
+            depths: 0,
+            increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i32) {
+        if self.ring.len() < self.window {
+            self.ring.push(depth);
+        } else {
+            // Consecutive windows share every depth but the one leaving and
+            // the one entering, so comparing those two compares the sums
+            if depth > self.ring[self.oldest] {
+                self.increases += 1;
+            }
+            self.ring[self.oldest] = depth;
+            self.oldest = (self.oldest + 1) % self.window;
+        }
+        self.depths += 1;
+    }
+
+    /// Depths fed so far
+    pub fn depths(&self) -> u64 {
+        self.depths
+    }
+
+    pub fn increases(&self) -> u64 {
+        self.increases
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<std::io::Error> for StreamError {
+    fn from(e: std::io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+/// Feeds every depth read from `reader` to `counter` and calls `report`
+/// after every `every` depths.
+///
+/// Reading stops at the end of `reader` or at a line holding `END_MARKER`.
+/// With `follow`, the end of `reader` is only where the data written so far
+/// ends: reading resumes after waiting that long, so that lines appended to
+/// a file keep being counted until the marker comes.
+pub fn run(
+    mut reader: impl BufRead,
+    counter: &mut IncreaseCounter,
+    every: u64,
+    follow: Option<Duration>,
+    mut report: impl FnMut(&IncreaseCounter),
+) -> Result<(), StreamError> {
+    let mut line = String::new();
+    let mut number = 0;
+
+    loop {
+        let read = reader.read_line(&mut line)?;
+        // A line without its newline may still be being written, unless it
+        // already is the marker, which is often written without one
+        if read == 0 || !line.ends_with('\n') {
+            if line.trim_end_matches('\r') == END_MARKER {
+                return Ok(());
+            }
+            if let Some(poll) = follow {
+                std::thread::sleep(poll);
+                continue;
+            }
+            if line.is_empty() {
+                return Ok(());
+            }
+        }
+
+        number += 1;
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text == END_MARKER {
+            return Ok(());
+        }
+        counter.push(parse_depth(&Line { number, text })?);
+        if counter.depths().is_multiple_of(every) {
+            report(counter);
+        }
+        line.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::count_increases;
+
+    const SAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn matches_the_whole_input_count() {
+        let depths = crate::parse_input(SAMPLE).unwrap();
+        for window in 1..=4 {
+            let mut counter = IncreaseCounter::new(window);
+            run(SAMPLE.as_bytes(), &mut counter, 1, None, |_| {}).unwrap();
+            assert_eq!(counter.increases(), count_increases(&depths, window) as u64);
+        }
+    }
+
+    #[test]
+    fn reports_periodically_and_stops_at_the_marker() {
+        let input = format!("{}{}\n1000\n", SAMPLE, END_MARKER);
+        let mut counter = IncreaseCounter::new(1);
+        let mut reports = Vec::new();
+        run(input.as_bytes(), &mut counter, 4, None, |c| {
+            reports.push((c.depths(), c.increases()))
+        })
+        .unwrap();
+        assert_eq!(reports, [(4, 3), (8, 6)]);
+        assert_eq!((counter.depths(), counter.increases()), (10, 7));
+    }
+
+    #[test]
+    fn stops_at_a_marker_without_newline_when_following() {
+        let input = format!("1\n2\n{}", END_MARKER);
+        let mut counter = IncreaseCounter::new(1);
+        run(
+            input.as_bytes(),
+            &mut counter,
+            10,
+            Some(Duration::ZERO),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!((counter.depths(), counter.increases()), (2, 1));
+    }
+
+    #[test]
+    fn points_at_bad_lines() {
+        let mut counter = IncreaseCounter::new(3);
+        match run("1\n2\nx\n".as_bytes(), &mut counter, 10, None, |_| {}) {
+            Err(StreamError::Parse(e)) => assert_eq!((e.line, e.token.as_str()), (3, "x")),
+            _ => panic!("expected a parse error"),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use aoc_common::{input_lines, Params, ParseError, Rng, Solution};
+
+// Completion scores of lines with more unclosed chunks overflow a u64
+const MAX_UNCLOSED: usize = 27;
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut incomplete = 0;
+    let lines = input_lines(input)
+        .map(|line| {
+            let chunk = line.check_chars(|c| "([{<)]}>".contains(c), "a bracket")?;
+            if !common::is_corrupted(chunk) {
+                let opened = chunk.chars().filter(|c| "([{<".contains(*c)).count();
+                if 2 * opened - chunk.len() > MAX_UNCLOSED {
+                    return Err(line.end_error(format!("at most {} unclosed chunks", MAX_UNCLOSED)));
+                }
+                incomplete += 1;
+            }
+            Ok(chunk.to_string())
+        })
+        .collect::<Result<Vec<String>, ParseError>>()?;
+    // Part 2 takes the middle completion score
+    if incomplete == 0 {
+        return Err(ParseError::end_of_input(
+            input,
+            "a line that is not corrupted",
+        ));
+    }
+    Ok(lines)
+}
+
+mod common {
+    pub fn find_first_closing_delim(line: &str) -> Option<char> {
+        let mut s = Vec::<char>::with_capacity(line.len());
+
+        let lala = |c: char, s: &mut Vec<char>| {
+            if let Some(top) = s.pop() {
+                return match top {
+                    '(' => c == ')',
+                    '[' => c == ']',
+                    '{' => c == '}',
+                    '<' => c == '>',
+                    _ => panic!(),
+                };
+            }
+            false
+        };
+
+        for c in line.chars() {
+            match c {
+                '(' | '[' | '{' | '<' => s.push(c),
+                ')' | ']' | '}' | '>' => {
+                    if !lala(c, &mut s) {
+                        return Some(c);
+                    }
+                }
+                _ => panic!(),
+            }
+        }
+
+        None
+    }
+
+    pub fn is_corrupted(s: &str) -> bool {
+        find_first_closing_delim(s).is_some()
+    }
+}
+
+pub mod part1 {
+    use crate::common::*;
+
+    fn score_table(c: char) -> u32 {
+        match c {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            '>' => 25137,
+            _ => panic!(),
+        }
+    }
+
+    pub fn solve(line_list: &[String]) -> u32 {
+        let mut score = 0;
+        for line in line_list {
+            let result = find_first_closing_delim(line);
+            if let Some(c) = result {
+                score += score_table(c);
+            }
+        }
+        score
+    }
+}
+
+pub mod part2 {
+    use crate::common::*;
+
+    fn find_score(line: &str) -> u64 {
+        let mut s = Vec::<char>::with_capacity(line.len());
+
+        for c in line.chars() {
+            match c {
+                '(' | '[' | '{' | '<' => s.push(c),
+                _ => {
+                    s.pop().unwrap();
+                }
+            }
+        }
+
+        let mut score = 0;
+        for c in s.into_iter().rev() {
+            score *= 5;
+            score += match c {
+                '(' => 1,
+                '[' => 2,
+                '{' => 3,
+                '<' => 4,
+                _ => panic!(),
+            };
+        }
+
+        score
+    }
+
+    pub fn solve(line_list: &[String]) -> u64 {
+        let list = line_list
+            .iter()
+            .filter(|s| !is_corrupted(s))
+            .collect::<Vec<&String>>();
+
+        let mut score_list = list
+            .into_iter()
+            .map(|s| find_score(s))
+            .collect::<Vec<u64>>();
+        score_list.sort_unstable();
+        score_list[score_list.len() / 2]
+    }
+}
+
+const OPENING: [char; 4] = ['(', '[', '{', '<'];
+const CLOSING: [char; 4] = [')', ']', '}', '>'];
+
+// Chunks of generated lines nest at most this deep, so that completion
+// scores fit in a u64
+const MAX_DEPTH: usize = 20;
+
+/// `size` lines of chunks, about half of them corrupted and the others
+/// incomplete. The first line is always incomplete, so that part 2 has a
+/// middle score.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for i in 0..size {
+        let mut line = String::new();
+        let mut stack = Vec::new();
+        for _ in 0..rng.range(20..=100) {
+            if !stack.is_empty() && (stack.len() == MAX_DEPTH || rng.chance(2, 5)) {
+                line.push(CLOSING[stack.pop().unwrap()]);
+            } else {
+                let bracket = rng.below(4);
+                stack.push(bracket);
+                line.push(OPENING[bracket]);
+            }
+        }
+        if stack.is_empty() {
+            line.push(OPENING[rng.below(4)]);
+        } else if i > 0 && rng.chance(1, 2) {
+            // Close the innermost chunk with the wrong bracket, then carry on
+            // with any brackets at all
+            let top = *stack.last().unwrap();
+            let wrong = (top + 1 + rng.below(3)) % 4;
+            line.push(CLOSING[wrong]);
+            for _ in 0..rng.below(20) {
+                line.push(*rng.choose(&[OPENING, CLOSING].concat()));
+            }
+        }
+        result.push_str(&line);
+        result.push('\n');
+    }
+    result
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::solve(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> (usize, String) {
+        match parse_input(input) {
+            Ok(_) => panic!("{:?} parsed", input),
+            Err(error) => (error.line, error.expected),
+        }
+    }
+
+    #[test]
+    fn requires_a_line_to_complete() {
+        let expected = "a line that is not corrupted".to_string();
+        assert_eq!(parse_error("(]\n"), (2, expected.clone()));
+        assert_eq!(parse_error(""), (1, expected));
+        assert_eq!(part2::solve(&parse_input("(]\n[\n").unwrap()), 2);
+    }
+
+    #[test]
+    fn rejects_scores_overflowing() {
+        let line = "<".repeat(MAX_UNCLOSED);
+        assert_eq!(part2::solve(&parse_input(&line).unwrap()), 5u64.pow(27) - 1);
+        let (line, _) = parse_error(&format!("()\n{}<\n", line));
+        assert_eq!(line, 2);
+    }
+}
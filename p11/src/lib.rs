@@ -0,0 +1,142 @@
+use aoc_common::{Grid, Param, Params, ParseError, Pos, Rng, Solution};
+
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_digits(input)
+}
+
+fn try_flash(grid: &mut Grid<u32>, flashes: &mut Grid<bool>, pos: Pos) -> u32 {
+    if flashes[pos] {
+        return 0;
+    }
+    let mut counter = 1;
+    flashes[pos] = true;
+
+    for neighbour in grid.neighbours8(pos) {
+        grid[neighbour] += 1;
+
+        if grid[neighbour] > 9 {
+            counter += try_flash(grid, flashes, neighbour);
+        }
+    }
+
+    counter
+}
+
+/// Runs one step and returns the number of flashes.
+fn step(grid: &mut Grid<u32>) -> u32 {
+    let mut counter = 0;
+    let mut flashes = Grid::new(grid.width(), grid.height(), false);
+
+    grid.iter_mut().for_each(|x| *x += 1);
+
+    for pos in grid.positions() {
+        if grid[pos] > 9 {
+            counter += try_flash(grid, &mut flashes, pos);
+        }
+    }
+
+    grid.iter_mut().for_each(|x| {
+        if *x > 9 {
+            *x = 0;
+        }
+    });
+
+    counter
+}
+
+const STEPS: usize = 100;
+
+// Steps after which part 2 gives up on the octopuses ever flashing at once,
+// as some grids never do
+const MAX_SYNC_STEPS: u32 = 100_000;
+
+const NEVER_SYNCHRONIZED: &str = "the octopuses never all flash at once";
+
+/// Counts the flashes over `steps` steps.
+pub fn count_flashes(mut grid: Grid<u32>, steps: usize) -> u32 {
+    (0..steps).map(|_| step(&mut grid)).sum()
+}
+
+/// Steps until every octopus flashes at once, or `None` if they have not
+/// within `MAX_SYNC_STEPS`.
+pub fn synchronize(mut grid: Grid<u32>) -> Option<u32> {
+    (1..=MAX_SYNC_STEPS).find(|_| {
+        step(&mut grid);
+        grid.iter().all(|&x| x == 0)
+    })
+}
+
+// Steps within which the octopuses of a generated grid must all flash at once
+const GENERATE_MAX_STEPS: usize = 2000;
+
+/// A random `size` x `size` grid of energy levels. Grids are drawn until one
+/// synchronizes within 2000 steps, so that part 2 ends.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let grid = Grid::from_fn(size, size, |_| rng.below(10) as u32);
+        let mut octopuses = grid.clone();
+        if (0..GENERATE_MAX_STEPS).any(|_| {
+            step(&mut octopuses);
+            octopuses.iter().all(|&x| x == 0)
+        }) {
+            return grid.to_string();
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    // Energy levels, and the number of steps if given
+    type Input = (Grid<u32>, Option<usize>);
+    type Answer1 = u32;
+    type Answer2 = Result<u32, &'static str>;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        help: "steps to count flashes over in part 1 (default: 100)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 10;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((parse_input(input)?, params.get("steps")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        count_flashes(input.0.clone(), input.1.unwrap_or(STEPS))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        synchronize(input.0.clone()).ok_or(NEVER_SYNCHRONIZED)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "5483143223\n2745854711\n5264556173\n6141336146\n6357385478\n4167524645\n2176841721\n6882881134\n4846848554\n5283751526\n";
+
+    #[test]
+    fn answers_none_when_the_octopuses_never_synchronize() {
+        let grid = parse_input(SAMPLE).unwrap();
+        assert_eq!(count_flashes(grid.clone(), 100), 1656);
+        assert_eq!(synchronize(grid), Some(195));
+
+        // These two fall into a cycle without ever flashing together
+        let grid = parse_input("29\n").unwrap();
+        assert_eq!(synchronize(grid), None);
+    }
+}
@@ -0,0 +1,303 @@
+use aoc_common::{input_lines, Line, Params, ParseError, Rng, Solution};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::rc::Rc;
+
+struct Node {
+    key: String,
+    edges: Vec<Rc<RefCell<Node>>>,
+}
+
+pub struct Graph {
+    nodes_map: HashMap<String, Rc<RefCell<Node>>>,
+}
+
+fn is_small(key: &str) -> bool {
+    key.chars().next().unwrap().is_lowercase()
+}
+
+impl Node {
+    fn new(k: &str) -> Self {
+        Node {
+            key: k.to_string(),
+            edges: Vec::new(),
+        }
+    }
+
+    fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    fn is_start(&self) -> bool {
+        self.key == "start"
+    }
+
+    fn is_end(&self) -> bool {
+        self.key == "end"
+    }
+
+    fn is_small(&self) -> bool {
+        is_small(&self.key)
+    }
+
+    fn add_edge(&mut self, to: Rc<RefCell<Node>>) {
+        self.edges.push(to);
+    }
+
+    fn get_edges(&self) -> &Vec<Rc<RefCell<Node>>> {
+        &self.edges
+    }
+}
+
+impl Graph {
+    fn new() -> Self {
+        Graph {
+            nodes_map: HashMap::new(),
+        }
+    }
+
+    fn add_node(&mut self, key: &str) -> Rc<RefCell<Node>> {
+        let node = Rc::new(RefCell::new(Node::new(key)));
+        self.nodes_map.insert(key.to_string(), node.clone());
+        node
+    }
+
+    fn get_node(&self, key: &str) -> Option<Rc<RefCell<Node>>> {
+        self.nodes_map.get(key).cloned()
+    }
+
+    fn get_or_add_node(&mut self, key: &str) -> Rc<RefCell<Node>> {
+        if let Some(node) = self.nodes_map.get(key) {
+            return node.clone();
+        }
+        self.add_node(key)
+    }
+
+    fn get_start(&self) -> Option<Rc<RefCell<Node>>> {
+        self.get_node("start")
+    }
+}
+
+fn parse_cave<'a>(line: &Line<'a>, token: &'a str) -> Result<&'a str, ParseError> {
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(line.error(token, "a cave name"));
+    }
+    Ok(token)
+}
+
+pub fn parse_input(input: &str) -> Result<Graph, ParseError> {
+    let mut g = Graph::new();
+    for line in input_lines(input) {
+        let mut tokenizer = line.text.split('-');
+        let from = parse_cave(&line, line.next(&mut tokenizer, "a cave name")?)?;
+        let to = parse_cave(&line, line.next(&mut tokenizer, "`-`")?)?;
+        line.finish(&mut tokenizer)?;
+        let from_node = g.get_or_add_node(from);
+        if !from_node.borrow().is_small() && !is_small(to) {
+            // Paths could go back and forth between them forever
+            return Err(line.error(to, "a small cave, as two big caves are linked"));
+        }
+        let to_node = g.get_or_add_node(to);
+
+        from_node.borrow_mut().add_edge(to_node.clone());
+        to_node.borrow_mut().add_edge(from_node.clone());
+    }
+    for cave in ["start", "end"] {
+        if g.get_node(cave).is_none() {
+            return Err(ParseError::end_of_input(
+                input,
+                format!("a link to the `{}` cave", cave),
+            ));
+        }
+    }
+    Ok(g)
+}
+
+pub mod part1 {
+    use crate::*;
+
+    fn visit(node: Rc<RefCell<Node>>, visited: &mut HashSet<String>, counter: &mut u32) {
+        let node_borrowed = node.borrow();
+        let key = node_borrowed.get_key();
+        if node_borrowed.is_small() && visited.contains(key) {
+            return;
+        }
+        visited.insert(key.to_string());
+        if node_borrowed.is_end() {
+            *counter += 1;
+        } else if !node_borrowed.is_start() {
+            let edges = node_borrowed.get_edges();
+            for to in edges {
+                visit(to.clone(), visited, counter);
+            }
+        }
+        visited.remove(key);
+    }
+
+    pub fn solve(g: &Graph) -> u32 {
+        let start_node = g.get_start().unwrap();
+        let mut visited = HashSet::<String>::new();
+        let mut counter = 0;
+        visited.insert(start_node.borrow().get_key().to_string());
+        for to in start_node.borrow().get_edges() {
+            visit(to.clone(), &mut visited, &mut counter);
+        }
+        visited.remove(start_node.borrow().get_key());
+
+        counter
+    }
+}
+
+pub mod part2 {
+    use crate::*;
+
+    fn visit(
+        node: Rc<RefCell<Node>>,
+        visited: &mut HashSet<String>,
+        mut spare: bool,
+        counter: &mut u32,
+    ) {
+        let node_borrowed = node.borrow();
+        let key = node_borrowed.get_key();
+        let mut spare_used = false;
+        if node_borrowed.is_small() && visited.contains(key) {
+            if spare {
+                spare = false;
+                spare_used = true;
+            } else {
+                return;
+            }
+        }
+        visited.insert(key.to_string());
+        if node_borrowed.is_end() {
+            *counter += 1;
+        } else if !node_borrowed.is_start() {
+            let edges = node_borrowed.get_edges();
+            for to in edges {
+                visit(to.clone(), visited, spare, counter);
+            }
+        }
+        if !spare_used {
+            visited.remove(key);
+        }
+    }
+
+    pub fn solve(g: &Graph) -> u32 {
+        let start_node = g.get_start().unwrap();
+        let mut visited = HashSet::<String>::new();
+        let mut counter = 0;
+        visited.insert(start_node.borrow().get_key().to_string());
+        for to in start_node.borrow().get_edges() {
+            visit(to.clone(), &mut visited, true, &mut counter);
+        }
+        visited.remove(start_node.borrow().get_key());
+
+        counter
+    }
+}
+
+/// A connected cave system of `size` caves besides `start` and `end`, about a
+/// quarter of them big. Big caves are never linked to each other, which
+/// would make for endless paths. Cave names are two letters long, which caps
+/// `size` at 676.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = vec!["start".to_string()];
+    for i in 0..size.min(26 * 26) {
+        let name = [i / 26, i % 26]
+            .iter()
+            .map(|&c| (b'a' + c as u8) as char)
+            .collect::<String>();
+        names.push(if rng.chance(1, 4) {
+            name.to_uppercase()
+        } else {
+            name
+        });
+    }
+    names.push("end".to_string());
+    let is_big = names
+        .iter()
+        .map(|name| name.chars().all(|c| c.is_ascii_uppercase()))
+        .collect::<Vec<bool>>();
+
+    // Link every cave to one before it, so that all are connected, then add
+    // about as many links again
+    let mut links = BTreeSet::new();
+    for i in 1..names.len() {
+        let candidates = (0..i)
+            .filter(|&j| !(is_big[i] && is_big[j]))
+            .collect::<Vec<usize>>();
+        links.insert((*rng.choose(&candidates), i));
+    }
+    for _ in 0..names.len() {
+        let (a, b) = (rng.below(names.len()), rng.below(names.len()));
+        if a != b && !(is_big[a] && is_big[b]) {
+            links.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut links = links.into_iter().collect::<Vec<(usize, usize)>>();
+    rng.shuffle(&mut links);
+    links
+        .into_iter()
+        .map(|(a, b)| {
+            if rng.chance(1, 2) {
+                format!("{}-{}\n", names[a], names[b])
+            } else {
+                format!("{}-{}\n", names[b], names[a])
+            }
+        })
+        .collect()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Graph;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const GENERATE_SIZE: usize = 10;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::solve(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> (usize, String) {
+        match parse_input(input) {
+            Ok(_) => panic!("{:?} parsed", input),
+            Err(error) => (error.line, error.token),
+        }
+    }
+
+    #[test]
+    fn requires_start_and_end_caves() {
+        assert_eq!(parse_error("a-b\n"), (2, String::new()));
+        assert_eq!(parse_error("start-b\n"), (2, String::new()));
+        assert_eq!(parse_error(""), (1, String::new()));
+        assert!(parse_input("start-end\n").is_ok());
+    }
+
+    #[test]
+    fn rejects_linked_big_caves() {
+        assert_eq!(parse_error("start-A\nA-B\nB-end\n"), (2, "B".to_string()));
+    }
+}
@@ -0,0 +1,251 @@
+use aoc_common::{input_lines, Grid, Params, ParseError, Rng, Solution};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FoldAlong {
+    X,
+    Y,
+}
+
+pub type Paper = Grid<bool>;
+
+pub fn parse_input(input: &str) -> Result<(Paper, Vec<(FoldAlong, usize)>), ParseError> {
+    let mut it = input_lines(input);
+    let coord_list = it
+        .by_ref()
+        .take_while(|line| !line.text.is_empty())
+        .map(|line| {
+            let mut tokenizer = line.text.split(',');
+            let x = line.parse_next::<usize>(&mut tokenizer, "an x coordinate")?;
+            let y = line.parse_next::<usize>(&mut tokenizer, "a y coordinate")?;
+            line.finish(&mut tokenizer)?;
+            Ok((x, y))
+        })
+        .collect::<Result<Vec<(usize, usize)>, ParseError>>()?;
+
+    if coord_list.is_empty() {
+        return Err(ParseError::new(1, 1, "", "a dot"));
+    }
+
+    let largest_x = coord_list.iter().map(|(x, _)| x).max().unwrap();
+    let largest_y = coord_list.iter().map(|(_, y)| y).max().unwrap();
+    let mut result = Grid::new(largest_x + 1, largest_y + 1, false);
+
+    coord_list
+        .into_iter()
+        .for_each(|(x, y)| result[(y, x)] = true);
+
+    let fold_list = it
+        .map(|line| {
+            let mut tokenizer1 = line.text.split_whitespace();
+            line.literal(&mut tokenizer1, "fold")?;
+            line.literal(&mut tokenizer1, "along")?;
+            let description = line.next(&mut tokenizer1, "a fold line")?;
+            line.finish(&mut tokenizer1)?;
+            let mut tokenizer2 = description.split('=');
+            let axis = match line.next(&mut tokenizer2, "an axis")? {
+                "x" => FoldAlong::X,
+                "y" => FoldAlong::Y,
+                axis => return Err(line.error(axis, "`x` or `y`")),
+            };
+            let amount = line.parse_next::<usize>(&mut tokenizer2, "`=`")?;
+            line.finish(&mut tokenizer2)?;
+            Ok((axis, amount))
+        })
+        .collect::<Result<Vec<(FoldAlong, usize)>, ParseError>>()?;
+
+    Ok((result, fold_list))
+}
+
+pub fn render_paper(paper: &Paper) -> String {
+    paper.map(|&dot| if dot { '#' } else { '.' }).to_string()
+}
+
+// Capital letters in the 4x6 dot font used by the puzzle, row by row. Letters
+// are laid out side by side with a blank column between them.
+const GLYPHS: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+pub fn read_letters(paper: &Paper) -> Option<String> {
+    if paper.height() != GLYPH_HEIGHT {
+        return None;
+    }
+
+    (0..paper.width())
+        .step_by(GLYPH_WIDTH + 1)
+        .map(|base| {
+            let glyph = paper
+                .rows()
+                .flat_map(|row| (base..base + GLYPH_WIDTH).map(move |x| row.get(x) == Some(&true)))
+                .map(|dot| if dot { '#' } else { '.' })
+                .collect::<String>();
+            GLYPHS
+                .iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+pub fn solve<'a>(
+    paper: &mut Paper,
+    fold_iter: impl Iterator<Item = &'a (FoldAlong, usize)>,
+) -> usize {
+    for fold in fold_iter {
+        let base = fold.1;
+        // Each dot of the kept half is merged with its mirror image, if any
+        *paper = if fold.0 == FoldAlong::X {
+            Grid::from_fn(base, paper.height(), |(y, x)| {
+                paper[(y, x)] || paper.get((y, 2 * base - x)) == Some(&true)
+            })
+        } else {
+            Grid::from_fn(paper.width(), base, |(y, x)| {
+                paper[(y, x)] || paper.get((2 * base - y, x)) == Some(&true)
+            })
+        };
+    }
+    paper.iter().filter(|&&x| x).count()
+}
+
+pub fn part1(paper: &mut Paper, fold_list: &[(FoldAlong, usize)]) -> usize {
+    let fold_iter = fold_list.iter().take(1);
+    solve(paper, fold_iter)
+}
+
+pub fn part2(paper: &mut Paper, fold_list: &[(FoldAlong, usize)]) -> String {
+    let fold_iter = fold_list.iter();
+    solve(paper, fold_iter);
+    read_letters(paper).unwrap_or_else(|| render_paper(paper))
+}
+
+// Generated instructions fold the paper this many times along each axis, as
+// in the puzzle
+const GENERATE_FOLDS_X: usize = 5;
+const GENERATE_FOLDS_Y: usize = 7;
+// Letters spelled by generated instructions
+const GENERATE_LETTERS: usize = 8;
+
+/// Instructions spelling eight random letters, with at least `size` dots.
+///
+/// Dots are picked among those of the letters and unfolded at random, so
+/// none falls on a fold line. A dot in the far corner of the paper makes it
+/// as large as the folds expect.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // The top left dot of the first letter is the far corner, folded
+    let mut letters = (0..GENERATE_LETTERS)
+        .map(|_| rng.choose(GLYPHS).1)
+        .collect::<Vec<&str>>();
+    let corner_glyphs = GLYPHS
+        .iter()
+        .filter(|(_, glyph)| glyph.starts_with('#'))
+        .collect::<Vec<_>>();
+    letters[0] = rng.choose(&corner_glyphs).1;
+    let letter_dots = letters
+        .iter()
+        .enumerate()
+        .flat_map(|(i, glyph)| {
+            glyph
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(j, _)| (i * (GLYPH_WIDTH + 1) + j % GLYPH_WIDTH, j / GLYPH_WIDTH))
+        })
+        .collect::<Vec<(usize, usize)>>();
+
+    let mut axes = [
+        vec![FoldAlong::X; GENERATE_FOLDS_X],
+        vec![FoldAlong::Y; GENERATE_FOLDS_Y],
+    ]
+    .concat();
+    rng.shuffle(&mut axes);
+    // Unfold from the letters' size back to the paper's
+    let (mut width, mut height) = (GENERATE_LETTERS * (GLYPH_WIDTH + 1) - 1, GLYPH_HEIGHT);
+    let mut folds = Vec::new();
+    for &axis in axes.iter().rev() {
+        let size = if axis == FoldAlong::X {
+            &mut width
+        } else {
+            &mut height
+        };
+        folds.push((axis, *size));
+        *size = 2 * *size + 1;
+    }
+    folds.reverse();
+
+    let mut dots = BTreeSet::new();
+    dots.insert((width - 1, height - 1));
+    let mut next = 0;
+    while dots.len() < size.max(letter_dots.len() + 1) {
+        // Every dot of the letters comes first
+        let (mut x, mut y) = letter_dots[next % letter_dots.len()];
+        next += 1;
+        for &(axis, line) in folds.iter().rev() {
+            if rng.chance(1, 2) {
+                match axis {
+                    FoldAlong::X => x = 2 * line - x,
+                    FoldAlong::Y => y = 2 * line - y,
+                }
+            }
+        }
+        dots.insert((x, y));
+    }
+
+    let mut dots = dots.into_iter().collect::<Vec<(usize, usize)>>();
+    rng.shuffle(&mut dots);
+    let mut result = dots
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect::<String>();
+    result.push('\n');
+    for (axis, line) in folds {
+        let axis = if axis == FoldAlong::X { 'x' } else { 'y' };
+        result.push_str(&format!("fold along {}={}\n", axis, line));
+    }
+    result
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = (Paper, Vec<(FoldAlong, usize)>);
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    const GENERATE_SIZE: usize = 800;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&mut input.0.clone(), &input.1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&mut input.0.clone(), &input.1)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
@@ -0,0 +1,303 @@
+use aoc_common::{input_lines, Line, Param, Params, ParseError, Rng, Solution};
+use std::collections::{HashMap, HashSet};
+
+fn parse_elements<'a>(line: &Line<'a>, token: &'a str, len: usize) -> Result<&'a str, ParseError> {
+    let expected = if len == 1 {
+        "an element".to_string()
+    } else {
+        format!("{} elements", len)
+    };
+    if token.len() != len || !token.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(line.error(token, expected));
+    }
+    Ok(token)
+}
+
+pub fn parse_input(input: &str) -> Result<(String, HashMap<String, String>), ParseError> {
+    let mut line_iterator = input_lines(input);
+
+    let template_line = line_iterator
+        .by_ref()
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a polymer template"))?;
+    let template = template_line.check_chars(|c| c.is_ascii_uppercase(), "an element")?;
+    if template.is_empty() {
+        return Err(template_line.end_error("an element"));
+    }
+
+    if let Some(separator) = line_iterator.next() {
+        separator.blank()?;
+    }
+
+    let rules = line_iterator
+        .map(|line| {
+            let mut tokenizer = line.text.split_whitespace();
+            let from = parse_elements(&line, line.next(&mut tokenizer, "a pair")?, 2)?;
+            line.literal(&mut tokenizer, "->")?;
+            let to = parse_elements(&line, line.next(&mut tokenizer, "an element")?, 1)?;
+            line.finish(&mut tokenizer)?;
+            Ok((from.to_string(), to.to_string()))
+        })
+        .collect::<Result<HashMap<String, String>, ParseError>>()?;
+
+    // Every pair the polymer can come to hold needs a rule: those of the
+    // template, and those each insertion makes
+    let mut seen = HashSet::new();
+    let mut pending = Vec::new();
+    for i in 0..template.len() - 1 {
+        let pair = &template[i..i + 2];
+        if !rules.contains_key(pair) {
+            return Err(template_line.error(pair, format!("a rule for `{}`", pair)));
+        }
+        if seen.insert(pair.to_string()) {
+            pending.push(pair.to_string());
+        }
+    }
+    while let Some(pair) = pending.pop() {
+        let inserted = &rules[&pair];
+        for next in [
+            format!("{}{}", &pair[..1], inserted),
+            format!("{}{}", inserted, &pair[1..]),
+        ] {
+            if !rules.contains_key(&next) {
+                return Err(ParseError::end_of_input(
+                    input,
+                    format!(
+                        "a rule for `{}`, which `{} -> {}` makes",
+                        next, pair, inserted
+                    ),
+                ));
+            }
+            if seen.insert(next.clone()) {
+                pending.push(next);
+            }
+        }
+    }
+
+    Ok((template.to_string(), rules))
+}
+
+const PART1_STEPS: usize = 10;
+const PART2_STEPS: usize = 40;
+
+// Part 1 builds the polymer itself while it stays shorter than this, and
+// counts pairs like part 2 beyond
+const EXPAND_LIMIT: usize = 1 << 22;
+
+// Length of the polymer grown from `template` after `num_steps` steps, if
+// below `EXPAND_LIMIT`
+fn expanded_len(template: &str, num_steps: usize) -> Option<usize> {
+    let shift = u32::try_from(num_steps).ok().filter(|&n| n < usize::BITS)?;
+    let pairs = (template.len() - 1).checked_mul(1 << shift)?;
+    Some(pairs + 1).filter(|&len| len < EXPAND_LIMIT)
+}
+
+pub fn part1(template: &str, rules: &HashMap<String, String>, num_steps: usize) -> Option<u128> {
+    if expanded_len(template, num_steps).is_none() {
+        return part2(template, rules, num_steps);
+    }
+    let mut current = template.to_string();
+
+    for _ in 0..num_steps {
+        let mut iterator = current.chars();
+        let mut next_state = String::with_capacity(current.len());
+        next_state.push(iterator.clone().next().unwrap());
+
+        for _ in 0..current.len() - 1 {
+            let segment = iterator.clone().take(2).collect::<String>();
+            let rule = &rules[&segment];
+            next_state.push_str(rule);
+            next_state.push(segment.chars().nth(1).unwrap());
+            iterator.next();
+        }
+
+        current = next_state;
+    }
+
+    let mut counter_list = [None; 'Z' as usize - 'A' as usize + 1];
+    current.chars().for_each(|c| {
+        *counter_list[c as usize - 'A' as usize].get_or_insert(0) += 1;
+    });
+
+    let max = counter_list.iter().filter_map(|&c| c).max().unwrap();
+    let min = counter_list.iter().filter_map(|&c| c).min().unwrap();
+
+    Some(max - min)
+}
+
+/// Difference between the most and least common elements after `num_steps`
+/// steps, counting pairs rather than building the polymer. `None` if the
+/// counts overflow.
+pub fn part2(template: &str, rules: &HashMap<String, String>, num_steps: usize) -> Option<u128> {
+    let mut status = HashMap::<String, u128>::new();
+    let mut iterator = template.chars();
+
+    for _ in 0..template.len() - 1 {
+        let segment = iterator.clone().take(2).collect::<String>();
+
+        status.entry(segment).and_modify(|x| *x += 1).or_insert(1);
+
+        iterator.next();
+    }
+
+    for _ in 0..num_steps {
+        let mut new_status = HashMap::<String, u128>::with_capacity(status.len());
+        for (key, value) in status.drain() {
+            let rule = &rules[&key];
+            let mut c_iterator = key.chars();
+            let c1 = c_iterator.by_ref().next().unwrap();
+            let c2 = c_iterator.next().unwrap();
+
+            let mut segment1 = String::from(c1);
+            segment1.push_str(rule);
+            let mut segment2 = rule.clone();
+            segment2.push(c2);
+
+            for segment in [segment1, segment2] {
+                let count = new_status.entry(segment).or_insert(0);
+                *count = count.checked_add(value)?;
+            }
+        }
+        status = new_status;
+    }
+
+    let mut counter_list = [None::<u128>; 'Z' as usize - 'A' as usize + 1];
+
+    for (key, value) in status.drain() {
+        let c = key.chars().next().unwrap();
+        let count = counter_list[c as usize - 'A' as usize].get_or_insert(0);
+        *count = count.checked_add(value)?;
+    }
+    let last = counter_list[template.chars().next_back().unwrap() as usize - 'A' as usize]
+        .get_or_insert(0);
+    *last = last.checked_add(1)?;
+
+    let max = counter_list.iter().filter_map(|&c| c).max().unwrap();
+    let min = counter_list.iter().filter_map(|&c| c).min().unwrap();
+
+    Some(max - min)
+}
+
+const OVERFLOW: &str = "element counts overflow a u128";
+
+// Elements used by generated instructions, as in the puzzle
+const GENERATE_ELEMENTS: usize = 10;
+
+/// A random template of `size` elements, with an insertion rule for every
+/// pair of elements it may hold.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut elements = ('A'..='Z').collect::<Vec<char>>();
+    rng.shuffle(&mut elements);
+    elements.truncate(GENERATE_ELEMENTS);
+
+    let mut result = (0..size)
+        .map(|_| *rng.choose(&elements))
+        .collect::<String>();
+    result.push_str("\n\n");
+    let mut rules = elements
+        .iter()
+        .flat_map(|&a| elements.iter().map(move |&b| (a, b)))
+        .collect::<Vec<(char, char)>>();
+    rng.shuffle(&mut rules);
+    for (a, b) in rules {
+        result.push_str(&format!("{}{} -> {}\n", a, b, rng.choose(&elements)));
+    }
+    result
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    // Template, rules, and the number of steps if given
+    type Input = (String, HashMap<String, String>, Option<usize>);
+    type Answer1 = Result<u128, &'static str>;
+    type Answer2 = Result<u128, &'static str>;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        help: "insertion steps (default: 10 for part 1, 40 for part 2)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 20;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let (template, rules) = parse_input(input)?;
+        Ok((template, rules, params.get("steps")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.1, input.2.unwrap_or(PART1_STEPS)).ok_or(OVERFLOW)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, &input.1, input.2.unwrap_or(PART2_STEPS)).ok_or(OVERFLOW)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+    fn parse_error(input: &str) -> (usize, usize, String) {
+        match parse_input(input) {
+            Ok(_) => panic!("{:?} parsed", input),
+            Err(error) => (error.line, error.column, error.token),
+        }
+    }
+
+    #[test]
+    fn requires_a_rule_for_every_pair() {
+        assert_eq!(parse_error("ABC\n\nAB -> C\n"), (1, 2, "BC".to_string()));
+        // AB makes AC and CB
+        assert_eq!(
+            parse_error("AB\n\nAB -> C\nAC -> A\n"),
+            (5, 1, String::new())
+        );
+        assert!(parse_input("AB\n\nAB -> A\nAA -> A\n").is_ok());
+    }
+
+    #[test]
+    fn counts_pairs_for_long_polymers() {
+        let (template, rules) = parse_input(SAMPLE).unwrap();
+        for steps in [0, 10, 15] {
+            assert_eq!(
+                part1(&template, &rules, steps),
+                part2(&template, &rules, steps)
+            );
+        }
+        assert_eq!(expanded_len(&template, 21), None);
+        assert!(part1(&template, &rules, 100).is_some());
+        assert_eq!(part1(&template, &rules, 200), None);
+    }
+}
@@ -0,0 +1,170 @@
+use aoc_common::{Grid, Param, Params, ParseError, Rng, Solution};
+
+pub mod common {
+    use aoc_common::{Grid, ParseError, Pos};
+    use std::collections::BinaryHeap;
+
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    struct Element {
+        cost: i32,
+        i: usize,
+        j: usize,
+    }
+
+    impl Element {
+        fn new(cost: i32, i: usize, j: usize) -> Self {
+            Element { cost, i, j }
+        }
+    }
+
+    pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse_digits(input)
+    }
+
+    pub fn solve(
+        grid: &Grid<u32>,
+        rows: usize,
+        cols: usize,
+        get_grid_value: fn(&Grid<u32>, Pos) -> u32,
+    ) -> u32 {
+        let mut result = 0;
+        let mut q = BinaryHeap::<Element>::new();
+        let mut visited = Grid::new(cols, rows, false);
+        q.push(Element::new(0, 0, 0));
+
+        while let Some(elem) = q.pop() {
+            let Element { cost, i, j } = elem;
+
+            if visited[(i, j)] {
+                continue;
+            }
+            visited[(i, j)] = true;
+
+            if i == rows - 1 && j == cols - 1 {
+                result = (-cost) as u32;
+                break;
+            }
+
+            for (k, l) in visited.neighbours4((i, j)) {
+                let local_cost = -(get_grid_value(grid, (k, l)) as i32);
+                q.push(Element::new(cost + local_cost, k, l));
+            }
+        }
+
+        result
+    }
+}
+
+pub mod part1 {
+    use aoc_common::{Grid, Pos};
+
+    fn get_grid_value(grid: &Grid<u32>, pos: Pos) -> u32 {
+        grid[pos]
+    }
+
+    pub fn solve(grid: &Grid<u32>) -> u32 {
+        use crate::common;
+
+        common::solve(grid, grid.height(), grid.width(), get_grid_value)
+    }
+}
+
+pub mod part2 {
+    use aoc_common::{Grid, Pos};
+
+    fn get_grid_value(grid: &Grid<u32>, (i, j): Pos) -> u32 {
+        let norm_i = i % grid.height();
+        let norm_j = j % grid.width();
+        let tiles_i = i / grid.height();
+        let tiles_j = j / grid.width();
+        // Risk levels above 9 wrap back around to 1, however many tiles away
+        (grid[(norm_i, norm_j)] - 1 + (tiles_i + tiles_j) as u32) % 9 + 1
+    }
+
+    pub const TILE_FACTOR: usize = 5;
+
+    pub fn solve(grid: &Grid<u32>, tile_factor: usize) -> u32 {
+        use crate::common;
+
+        common::solve(
+            grid,
+            grid.height() * tile_factor,
+            grid.width() * tile_factor,
+            get_grid_value,
+        )
+    }
+}
+
+/// A random `size` x `size` map of risk levels.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    Grid::from_fn(size, size, |_| rng.range(1..=9)).to_string()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    // Risk levels, and the tile factor if given
+    type Input = (Grid<u32>, Option<usize>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "tile-factor",
+        help: "times the map is repeated in each direction in part 2 (default: 5)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((common::parse_input(input)?, params.get("tile-factor")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::solve(&input.0)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::solve(&input.0, input.1.unwrap_or(part2::TILE_FACTOR))
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_risk_levels_across_many_tiles() {
+        let grid = common::parse_input("9\n").unwrap();
+        assert_eq!(part2::solve(&grid, 5), 36);
+        assert_eq!(part2::solve(&grid, 12), 100);
+
+        let grid = common::parse_input("18\n23\n").unwrap();
+        let tiled = part2::solve(&grid, 11);
+        let expanded = (0..22)
+            .map(|i| {
+                (0..22)
+                    .map(|j| {
+                        let risk = grid[(i % 2, j % 2)] - 1 + (i / 2 + j / 2) as u32;
+                        char::from_digit(risk % 9 + 1, 10).unwrap()
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect::<String>();
+        assert_eq!(
+            part1::solve(&common::parse_input(&expanded).unwrap()),
+            tiled
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use aoc_common::{input_lines, Diagnostic, Line, Params, ParseError, Rng, Solution};
+
+pub fn parse_input(input: &str) -> Result<Packet, ParseError> {
+    let line = input_lines(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a transmission"))?;
+    let transmission = line.check_chars(
+        |c| c.is_ascii_digit() || ('A'..='F').contains(&c),
+        "a hexadecimal digit",
+    )?;
+    let mut bits = String::with_capacity(transmission.len() * 4);
+    transmission
+        .chars()
+        .for_each(|c| bits.push_str(Parser::base_convert(c)));
+    Parser::new(line, bits).parse_pkt()
+}
+
+/// A decoded packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u32,
+    pub type_id: u32,
+    /// Number of bits the packet takes, sub-packets included
+    pub bits: usize,
+    pub contents: Contents,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Contents {
+    Literal(i64),
+    Operator(Vec<Packet>),
+}
+
+impl Packet {
+    /// Sum of the versions of the packet and all its sub-packets.
+    pub fn total_version(&self) -> u32 {
+        let subpackets = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator(subpackets) => subpackets.iter().map(Packet::total_version).sum(),
+        };
+        self.version + subpackets
+    }
+
+    pub fn value(&self) -> i64 {
+        let subpackets = match &self.contents {
+            Contents::Literal(value) => return *value,
+            Contents::Operator(subpackets) => subpackets,
+        };
+        let op: fn(i64, i64) -> i64 = match self.type_id {
+            0 => |a, b| a + b,
+            1 => |a, b| a * b,
+            2 => std::cmp::min,
+            3 => std::cmp::max,
+            5 => |a, b| (a > b) as i64,
+            6 => |a, b| (a < b) as i64,
+            _ => |a, b| (a == b) as i64,
+        };
+        subpackets
+            .iter()
+            .map(Packet::value)
+            .reduce(op)
+            .expect("operators have sub-packets")
+    }
+}
+
+/// Decodes packets from the bits of a transmission, reporting errors at
+/// the offset of the offending bits, counted from 1, as their column.
+pub struct Parser<'a> {
+    line: Line<'a>,
+    bits: String,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn base_convert(s: char) -> &'static str {
+        match s {
+            '0' => "0000",
+            '1' => "0001",
+            '2' => "0010",
+            '3' => "0011",
+            '4' => "0100",
+            '5' => "0101",
+            '6' => "0110",
+            '7' => "0111",
+            '8' => "1000",
+            '9' => "1001",
+            'A' => "1010",
+            'B' => "1011",
+            'C' => "1100",
+            'D' => "1101",
+            'E' => "1110",
+            'F' => "1111",
+            _ => panic!(),
+        }
+    }
+
+    pub fn new(line: Line<'a>, bits: String) -> Self {
+        Parser {
+            line,
+            bits,
+            offset: 0,
+        }
+    }
+
+    // Error pointing at the bits from `start` to `end`
+    fn error(&self, start: usize, end: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::new(
+            self.line.number,
+            start + 1,
+            &self.bits[start..end],
+            expected,
+        )
+    }
+
+    // Reads the next `len` bits as a number
+    fn take(&mut self, len: usize, expected: &str) -> Result<u64, ParseError> {
+        if self.bits.len() - self.offset < len {
+            let end = self.bits.len();
+            return Err(self.error(end, end, expected));
+        }
+        let bits = &self.bits[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(u64::from_str_radix(bits, 2).unwrap())
+    }
+
+    fn parse_literal(&mut self) -> Result<i64, ParseError> {
+        let start = self.offset;
+        let mut result = 0i64;
+        let mut is_continuation = true;
+
+        while is_continuation {
+            is_continuation = self.take(1, "a literal group")? == 1;
+            let digits = self.take(4, "four bits of a literal")? as i64;
+            result = result
+                .checked_mul(16)
+                .map(|result| result + digits)
+                .ok_or_else(|| {
+                    self.error(start, self.offset, "a literal value that fits in an i64")
+                })?;
+        }
+
+        Ok(result)
+    }
+
+    fn parse_operator(&mut self, type_id: u32) -> Result<Vec<Packet>, ParseError> {
+        let start = self.offset;
+        let length_type_id = self.take(1, "a length type ID")?;
+        let mut subpackets = Vec::new();
+        let header_end;
+
+        if length_type_id == 0 {
+            let total_length = self.take(15, "a length in bits")? as usize;
+            header_end = self.offset;
+            let end = self.offset + total_length;
+            while self.offset < end {
+                subpackets.push(self.parse_pkt()?);
+            }
+            if self.offset > end {
+                let expected = format!("sub-packets taking {} bits", total_length);
+                return Err(self.error(start, header_end, expected));
+            }
+        } else {
+            let num_subpackets = self.take(11, "a number of sub-packets")?;
+            header_end = self.offset;
+            for _ in 0..num_subpackets {
+                subpackets.push(self.parse_pkt()?);
+            }
+        }
+
+        if subpackets.is_empty() {
+            return Err(self.error(start, header_end, "at least one sub-packet"));
+        }
+        if type_id >= 5 && subpackets.len() != 2 {
+            let expected = "exactly two sub-packets to compare";
+            return Err(self.error(start, header_end, expected));
+        }
+        Ok(subpackets)
+    }
+
+    pub fn parse_pkt(&mut self) -> Result<Packet, ParseError> {
+        let start = self.offset;
+        let version = self.take(3, "a packet version")? as u32;
+        let type_id = self.take(3, "a packet type ID")? as u32;
+
+        let contents = match type_id {
+            4 => Contents::Literal(self.parse_literal()?),
+            _ => Contents::Operator(self.parse_operator(type_id)?),
+        };
+
+        Ok(Packet {
+            version,
+            type_id,
+            bits: self.offset - start,
+            contents,
+        })
+    }
+}
+
+pub fn part1(packet: &Packet) -> u32 {
+    packet.total_version()
+}
+
+pub fn part2(packet: &Packet) -> i64 {
+    packet.value()
+}
+
+// A random packet made of `size` packets in all, as bits, and its value.
+// Operators whose value would overflow become maximums instead.
+fn generate_packet(rng: &mut Rng, size: usize) -> (String, i64) {
+    let mut bits = format!("{:03b}", rng.below(8));
+
+    if size == 1 {
+        bits.push_str("100");
+        let groups = rng.range(1..=8);
+        let mut value = 0;
+        for group in 1..=groups {
+            let nibble = rng.below(16);
+            bits.push(if group < groups { '1' } else { '0' });
+            bits.push_str(&format!("{:04b}", nibble));
+            value = value * 16 + nibble as i64;
+        }
+        return (bits, value);
+    }
+
+    // Comparisons take exactly two subpackets
+    let mut type_id = if size >= 3 {
+        *rng.choose(&[0, 1, 2, 3, 5, 6, 7])
+    } else {
+        rng.below(4)
+    };
+    let count = if type_id >= 5 {
+        2
+    } else {
+        rng.below(5.min(size - 1)) + 1
+    };
+    let mut sizes = vec![1; count];
+    for _ in count..size - 1 {
+        sizes[rng.below(count)] += 1;
+    }
+    let subpackets = sizes
+        .into_iter()
+        .map(|size| generate_packet(rng, size))
+        .collect::<Vec<(String, i64)>>();
+
+    let values = subpackets.iter().map(|(_, value)| *value);
+    let max = values.clone().max().unwrap();
+    let value = match type_id {
+        0 => values.clone().try_fold(0i64, |acc, v| acc.checked_add(v)),
+        1 => values.clone().try_fold(1i64, |acc, v| acc.checked_mul(v)),
+        2 => values.clone().min(),
+        3 => Some(max),
+        _ => {
+            let (a, b) = (subpackets[0].1, subpackets[1].1);
+            Some(match type_id {
+                5 => (a > b) as i64,
+                6 => (a < b) as i64,
+                _ => (a == b) as i64,
+            })
+        }
+    };
+    let value = value.unwrap_or_else(|| {
+        type_id = 3;
+        max
+    });
+
+    let subpacket_bits = subpackets
+        .into_iter()
+        .map(|(bits, _)| bits)
+        .collect::<String>();
+    bits.push_str(&format!("{:03b}", type_id));
+    if subpacket_bits.len() < 1 << 15 && rng.chance(1, 2) {
+        bits.push_str(&format!("0{:015b}", subpacket_bits.len()));
+    } else {
+        bits.push_str(&format!("1{:011b}", count));
+    }
+    bits.push_str(&subpacket_bits);
+    (bits, value)
+}
+
+/// A random transmission of `size` packets in all.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let (mut bits, _) = generate_packet(rng, size);
+    while bits.len() % 4 != 0 {
+        bits.push('0');
+    }
+    let mut result = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let nibble = std::str::from_utf8(nibble).unwrap();
+            format!("{:X}", u8::from_str_radix(nibble, 2).unwrap())
+        })
+        .collect::<String>();
+    result.push('\n');
+    result
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = Packet;
+    type Answer1 = u32;
+    type Answer2 = i64;
+
+    const GENERATE_SIZE: usize = 300;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn diagnostics(input: &Self::Input, _part: u8) -> Vec<Diagnostic> {
+        vec![Diagnostic::new("bits_read", input.bits)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_packets() {
+        let packet = parse_input("8A004A801A8002F478\n").unwrap();
+        assert_eq!(part1(&packet), 16);
+        let packet = parse_input("9C0141080250320F1802104A08\n").unwrap();
+        assert_eq!((part2(&packet), packet.bits), (1, 102));
+        let packet = parse_input("D2FE28\n").unwrap();
+        assert_eq!(packet.contents, Contents::Literal(2021));
+        let largest = parse_input("12FFFFFFFFFFFFFFFFFFBC\n").unwrap();
+        assert_eq!(part2(&largest), i64::MAX);
+    }
+
+    #[test]
+    fn rejects_malformed_packets() {
+        // Columns count bits, and tokens are made of them
+        let error = |input| {
+            let error = parse_input(input).unwrap_err();
+            (error.column, error.token.len(), error.expected)
+        };
+
+        // Cut short, at the bit where the input ends
+        assert_eq!(
+            error("9C0141080250320018021064A08\n"),
+            (109, 0, "a length in bits".to_string())
+        );
+        assert_eq!(error("3800\n"), (17, 0, "a length in bits".to_string()));
+
+        // Operators without sub-packets or comparing three, pointing at the
+        // length of their sub-packets
+        assert_eq!(
+            error("38000000\n"),
+            (7, 16, "at least one sub-packet".to_string())
+        );
+        assert_eq!(
+            error("3600C40881102\n"),
+            (7, 12, "exactly two sub-packets to compare".to_string())
+        );
+
+        // Sub-packets running past their operator's length, and a literal
+        // too large for an i64
+        assert_eq!(
+            error("200014408\n"),
+            (7, 16, "sub-packets taking 5 bits".to_string())
+        );
+        assert_eq!(
+            error("13FFFFFFFFFFFFFFFFFFFDE\n"),
+            (7, 80, "a literal value that fits in an i64".to_string())
+        );
+    }
+}
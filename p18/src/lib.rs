@@ -0,0 +1,480 @@
+// This solution parses snailfish numbers using an LL(1) parser into expression
+// trees. These expression trees are then manipulated by explosions, splits,
+// adds and reduces.
+
+// I strongly suspect that another solution using just a cursor over snailfish
+// number strings that would do string manipulation over them would have been
+// much much simpler. But I wanted to practice complex data structures in Rust,
+// like expression trees.
+use aoc_common::{input_lines, Params, ParseError, Rng, Solution};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+pub type RcNode = Rc<RefCell<Node>>;
+
+macro_rules! rcnode {
+    ($x : expr) => {
+        Rc::new(RefCell::<Node>::new($x))
+    };
+}
+
+mod llparser {
+    use crate::{Node, RcNode};
+    use aoc_common::{Line, ParseError};
+    use std::cell::RefCell;
+    use std::iter::Peekable;
+    use std::rc::Rc;
+    use std::str::CharIndices;
+
+    // Grammar:
+    // S -> N
+    // N -> [X,X]
+    // X -> num
+    // X -> N
+
+    type Stream<'a> = Peekable<CharIndices<'a>>;
+
+    pub fn parse(line: &Line) -> Result<RcNode, ParseError> {
+        let mut stream = line.text.char_indices().peekable();
+        rule_s(line, &mut stream)
+    }
+
+    fn next(line: &Line, stream: &mut Stream, expected: &str) -> Result<(usize, char), ParseError> {
+        stream.next().ok_or_else(|| line.end_error(expected))
+    }
+
+    fn rule_s(line: &Line, stream: &mut Stream) -> Result<RcNode, ParseError> {
+        let node = match stream.peek() {
+            Some((_, '[')) => rule_n(line, stream)?,
+            Some(&(i, _)) => return Err(line.char_error(i, "`[`")),
+            None => return Err(line.end_error("`[`")),
+        };
+        if let Some((i, _)) = stream.next() {
+            return Err(line.char_error(i, "end of line"));
+        }
+        Ok(node)
+    }
+
+    fn rule_n(line: &Line, stream: &mut Stream) -> Result<RcNode, ParseError> {
+        let (i, c) = next(line, stream, "`[`")?;
+        let x1 = match c {
+            '[' => rule_x(line, stream)?,
+            _ => return Err(line.char_error(i, "`[`")),
+        };
+
+        let (i, c) = next(line, stream, "`,`")?;
+        let x2 = match c {
+            ',' => rule_x(line, stream)?,
+            _ => return Err(line.char_error(i, "`,`")),
+        };
+
+        let (i, c) = next(line, stream, "`]`")?;
+        if c != ']' {
+            return Err(line.char_error(i, "`]`"));
+        }
+
+        let result = rcnode!(Node::new_pair(x1.clone(), x2.clone()));
+        x1.borrow_mut().parent = Some(result.clone());
+        x2.borrow_mut().parent = Some(result.clone());
+        Ok(result)
+    }
+
+    fn rule_x(line: &Line, stream: &mut Stream) -> Result<RcNode, ParseError> {
+        let node = match stream.peek() {
+            Some((_, '[')) => rule_n(line, stream)?,
+            Some(&(start, '0'..='9' | '-')) => {
+                let mut end = start;
+                while let Some(&(i, '0'..='9' | '-')) = stream.peek() {
+                    end = i + 1;
+                    stream.next();
+                }
+                let num = line.parse::<i32>(&line.text[start..end], "a number")?;
+                rcnode!(Node::new_regular(num))
+            }
+            Some(&(i, _)) => return Err(line.char_error(i, "`[` or a number")),
+            None => return Err(line.end_error("`[` or a number")),
+        };
+        Ok(node)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<RcNode>, ParseError> {
+    let numbers = input_lines(input)
+        .map(|line| llparser::parse(&line))
+        .collect::<Result<Vec<RcNode>, ParseError>>()?;
+    // Part 1 adds the others to the first one
+    if numbers.is_empty() {
+        return Err(ParseError::end_of_input(input, "a snailfish number"));
+    }
+    Ok(numbers)
+}
+
+#[derive(Clone)]
+enum NodePayload {
+    Regular(i32),
+    Pair { left: RcNode, right: RcNode },
+}
+
+#[derive(Clone)]
+pub struct Node {
+    payload: NodePayload,
+    parent: Option<RcNode>,
+}
+
+impl Node {
+    fn new_regular(v: i32) -> Self {
+        let payload = NodePayload::Regular(v);
+        Node {
+            payload,
+            parent: None,
+        }
+    }
+
+    fn new_pair(left: RcNode, right: RcNode) -> Self {
+        let payload = NodePayload::Pair { left, right };
+        Node {
+            payload,
+            parent: None,
+        }
+    }
+
+    fn is_same(n1: &Node, n2: &Node) -> bool {
+        std::ptr::eq(n1, n2)
+    }
+
+    fn get_parent(&self) -> Option<RcNode> {
+        self.parent.clone()
+    }
+
+    fn get_node_to_the_left(node: RcNode) -> Option<RcNode> {
+        let mut current = node;
+        let mut parent;
+
+        // Go up
+        loop {
+            parent = current.borrow().get_parent()?;
+            let parent_payload = &parent.borrow().payload;
+
+            if let NodePayload::Pair { right, .. } = parent_payload {
+                if Node::is_same(&current.borrow(), &right.borrow()) {
+                    break;
+                } else {
+                    current = parent.clone();
+                }
+            } else {
+                panic!();
+            }
+        }
+
+        // Go down and right
+        let left = match &parent.borrow().payload {
+            NodePayload::Pair { left, .. } => left.clone(),
+            _ => panic!(),
+        };
+        current = left;
+
+        loop {
+            let right = match &current.borrow().payload {
+                NodePayload::Regular(_) => return Some(current.clone()),
+                NodePayload::Pair { right, .. } => right.clone(),
+            };
+            current = right;
+        }
+    }
+
+    fn get_node_to_the_right(node: RcNode) -> Option<RcNode> {
+        let mut current = node;
+        let mut parent;
+
+        // Go up
+        loop {
+            parent = current.borrow().get_parent()?;
+            let parent_payload = &parent.borrow().payload;
+
+            if let NodePayload::Pair { left, .. } = parent_payload {
+                if Node::is_same(&current.borrow(), &left.borrow()) {
+                    break;
+                } else {
+                    current = parent.clone();
+                }
+            } else {
+                panic!();
+            }
+        }
+
+        // Go down and left
+        let right = match &parent.borrow().payload {
+            NodePayload::Pair { right, .. } => right.clone(),
+            _ => panic!(),
+        };
+        current = right;
+
+        loop {
+            let left = match &current.borrow().payload {
+                NodePayload::Regular(_) => return Some(current.clone()),
+                NodePayload::Pair { left, .. } => left.clone(),
+            };
+            current = left;
+        }
+    }
+
+    fn explode(node: RcNode) {
+        let node_to_the_left_opt = Node::get_node_to_the_left(node.clone());
+        let node_to_the_right_opt = Node::get_node_to_the_right(node.clone());
+
+        let (left, right) = match &node.borrow().payload {
+            NodePayload::Pair { left, right } => (left.clone(), right.clone()),
+            _ => panic!(),
+        };
+
+        let left_val = match left.borrow().payload {
+            NodePayload::Regular(v) => v,
+            _ => panic!(),
+        };
+        let right_val = match right.borrow().payload {
+            NodePayload::Regular(v) => v,
+            _ => panic!(),
+        };
+
+        if let Some(node_to_the_left) = node_to_the_left_opt {
+            match node_to_the_left.borrow_mut().payload {
+                NodePayload::Regular(ref mut v) => *v += left_val,
+                _ => panic!(),
+            }
+        }
+
+        if let Some(node_to_the_right) = node_to_the_right_opt {
+            match node_to_the_right.borrow_mut().payload {
+                NodePayload::Regular(ref mut v) => *v += right_val,
+                _ => panic!(),
+            }
+        }
+
+        node.borrow_mut().payload = NodePayload::Regular(0);
+    }
+
+    fn split(node: RcNode) {
+        let val = match node.borrow().payload {
+            NodePayload::Regular(v) => v,
+            _ => panic!(),
+        };
+        let left_val = val / 2;
+        let right_val = val - left_val;
+        let left_node = rcnode!(Node::new_regular(left_val));
+        let right_node = rcnode!(Node::new_regular(right_val));
+        node.borrow_mut().payload = NodePayload::Pair {
+            left: left_node.clone(),
+            right: right_node.clone(),
+        };
+        left_node.borrow_mut().parent = Some(node.clone());
+        right_node.borrow_mut().parent = Some(node);
+    }
+
+    fn add(n1: RcNode, n2: RcNode) -> RcNode {
+        let node = rcnode!(Node::new_pair(n1.clone(), n2.clone()));
+        n1.borrow_mut().parent = Some(node.clone());
+        n2.borrow_mut().parent = Some(node.clone());
+        node
+    }
+
+    fn reduce(root: RcNode) {
+        let mut changed = true;
+        while changed {
+            changed = Node::in_order_traversal_explode(root.clone(), 0);
+            if changed {
+                continue;
+            }
+            changed = Node::in_order_traversal_split(root.clone());
+        }
+    }
+
+    fn in_order_traversal_explode(node: RcNode, depth: usize) -> bool {
+        let (left, right) = match &node.borrow().payload {
+            NodePayload::Pair { left, right } => (left.clone(), right.clone()),
+            _ => return false,
+        };
+
+        if Node::in_order_traversal_explode(left.clone(), depth + 1) {
+            return true;
+        }
+
+        if depth > 3
+            && matches!(left.borrow().payload, NodePayload::Regular(_))
+            && matches!(right.borrow().payload, NodePayload::Regular(_))
+        {
+            Node::explode(node);
+            return true;
+        }
+
+        if Node::in_order_traversal_explode(right, depth + 1) {
+            return true;
+        }
+        false
+    }
+
+    fn in_order_traversal_split(node: RcNode) -> bool {
+        let should_split = matches!(node.borrow().payload, NodePayload::Regular(v) if v >= 10);
+
+        if should_split {
+            Node::split(node);
+            return true;
+        }
+
+        let (left, right) = match &node.borrow().payload {
+            NodePayload::Pair { left, right } => (left.clone(), right.clone()),
+            _ => return false,
+        };
+
+        if Node::in_order_traversal_split(left) {
+            return true;
+        }
+        if Node::in_order_traversal_split(right) {
+            return true;
+        }
+        false
+    }
+
+    fn magnitude(node: RcNode) -> i32 {
+        match &node.borrow().payload {
+            NodePayload::Regular(v) => *v,
+            NodePayload::Pair { left, right } => {
+                3 * Node::magnitude(left.clone()) + 2 * Node::magnitude(right.clone())
+            }
+        }
+    }
+
+    fn deep_clone(node: RcNode) -> RcNode {
+        match &node.borrow().payload {
+            NodePayload::Regular(v) => {
+                rcnode!(Node::new_regular(*v))
+            }
+            NodePayload::Pair { left, right } => {
+                let new_left = Node::deep_clone(left.clone());
+                let new_right = Node::deep_clone(right.clone());
+                let result = rcnode!(Node::new_pair(new_left.clone(), new_right.clone()));
+                new_left.borrow_mut().parent = Some(result.clone());
+                new_right.borrow_mut().parent = Some(result.clone());
+                result
+            }
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &self.payload {
+            NodePayload::Regular(v) => write!(f, "{}", v),
+            NodePayload::Pair { left, right } => {
+                write!(f, "[")?;
+                write!(f, "{}", left.borrow())?;
+                write!(f, ",")?;
+                write!(f, "{}", right.borrow())?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+// Additions rewire parent links in place, so every part works on a copy
+fn deep_clone_all(snailfish_numbers: &[RcNode]) -> Vec<RcNode> {
+    snailfish_numbers
+        .iter()
+        .map(|n| Node::deep_clone(n.clone()))
+        .collect()
+}
+
+pub fn part1(snailfish_numbers: &[RcNode]) -> i32 {
+    let base = snailfish_numbers[0].clone();
+    let result = snailfish_numbers.iter().skip(1).fold(base, |acc, node| {
+        let add_node = Node::add(acc, node.clone());
+        Node::reduce(add_node.clone());
+        add_node
+    });
+    Node::magnitude(result)
+}
+
+pub fn part2(snailfish_numbers: &[RcNode]) -> i32 {
+    let mut best = 0;
+    for (i, node_i) in snailfish_numbers.iter().enumerate() {
+        for (j, node_j) in snailfish_numbers.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let node_i_tmp = Node::deep_clone(node_i.clone());
+            let node_j_tmp = Node::deep_clone(node_j.clone());
+            let sum = Node::add(node_i_tmp, node_j_tmp);
+            Node::reduce(sum.clone());
+            let magnitude = Node::magnitude(sum);
+            if magnitude > best {
+                best = magnitude;
+            }
+        }
+    }
+    best
+}
+
+fn generate_number(rng: &mut Rng, depth: usize) -> String {
+    let element = |rng: &mut Rng| {
+        if depth < 4 && rng.chance(1, 2) {
+            generate_number(rng, depth + 1)
+        } else {
+            rng.below(10).to_string()
+        }
+    };
+    let left = element(rng);
+    let right = element(rng);
+    format!("[{},{}]", left, right)
+}
+
+/// `size` random snailfish numbers, already reduced: pairs nest at most four
+/// deep and regular numbers are single digits.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", generate_number(rng, 1)))
+        .collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    type Input = Vec<RcNode>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&deep_clone_all(input))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&deep_clone_all(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_a_number() {
+        let error = parse_input("").err().unwrap();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (1, "a snailfish number")
+        );
+        let numbers = parse_input("[[1,2],3]\n").unwrap();
+        assert_eq!((part1(&numbers), part2(&numbers)), (27, 0));
+    }
+}
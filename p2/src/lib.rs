@@ -0,0 +1,440 @@
+use aoc_common::{input_lines, Line, Params, ParseError, Rng, Solution};
+use std::fmt::{Display, Formatter};
+
+pub mod trajectory;
+
+/// Where the submarine is. Amounts are i32s and courses hold at most
+/// `MAX_COMMANDS` commands, so distances and aims stay below 2^55, and
+/// depths, which grow by aim times amount, below 2^110.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: i128,
+    pub y: i128,
+    pub aim: i128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+    Back(i32),
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Forward(amount) => write!(f, "forward {}", amount),
+            Command::Down(amount) => write!(f, "down {}", amount),
+            Command::Up(amount) => write!(f, "up {}", amount),
+            Command::Back(amount) => write!(f, "back {}", amount),
+        }
+    }
+}
+
+/// Most commands a course may expand to once its repeats are unrolled
+pub const MAX_COMMANDS: usize = 10_000_000;
+
+// A `repeat` block whose closing brace is still to come
+struct Block<'a> {
+    line: Line<'a>,
+    count_token: &'a str,
+    count: usize,
+    commands: Vec<Command>,
+}
+
+fn parse_command<'a>(
+    line: &Line<'a>,
+    direction: &str,
+    tokens: &mut impl Iterator<Item = &'a str>,
+) -> Result<Command, ParseError> {
+    let command = match direction {
+        "forward" => Command::Forward,
+        "down" => Command::Down,
+        "up" => Command::Up,
+        "back" => Command::Back,
+        _ => {
+            return Err(line.error(
+                direction,
+                "`forward`, `down`, `up`, `back`, `repeat` or `}`",
+            ))
+        }
+    };
+    let token = line.next(tokens, "an amount")?;
+    let amount = line.parse::<i32>(token, "an amount")?;
+    if amount < 0 {
+        return Err(line.error(token, "a non-negative amount"));
+    }
+    line.finish(tokens)?;
+    Ok(command(amount))
+}
+
+/// Parses a course, unrolling its `repeat N { ... }` blocks. Anything after
+/// a `#` is a comment, and blank lines are skipped.
+///
+/// Every bad line is reported, not just the first one.
+pub fn parse_program(input: &str) -> Result<Vec<Command>, Vec<ParseError>> {
+    let mut errors = Vec::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut commands = Vec::new();
+
+    for line in input_lines(input) {
+        let code = line.text.split('#').next().unwrap();
+        let line = Line { text: code, ..line };
+        let mut tokens = line.text.split_whitespace();
+        let result = match tokens.next() {
+            None => Ok(()),
+            Some("repeat") => {
+                let count_token = line.next(&mut tokens, "a repeat count");
+                let token = *count_token.as_ref().unwrap_or(&"");
+                let count = count_token
+                    .and_then(|token| line.parse::<usize>(token, "a repeat count"))
+                    .and_then(|count| {
+                        line.literal(&mut tokens, "{")?;
+                        line.finish(&mut tokens)?;
+                        Ok(count)
+                    });
+                // A bad block still opens, so that its `}` closes it
+                blocks.push(Block {
+                    line,
+                    count_token: token,
+                    count: *count.as_ref().unwrap_or(&0),
+                    commands: Vec::new(),
+                });
+                count.map(|_| ())
+            }
+            Some(token @ "}") => match blocks.pop() {
+                Some(block) => {
+                    let parent = blocks.last_mut().map_or(&mut commands, |b| &mut b.commands);
+                    let len = block.commands.len().saturating_mul(block.count);
+                    if parent.len().saturating_add(len) > MAX_COMMANDS {
+                        let expected =
+                            format!("a count keeping the course under {} commands", MAX_COMMANDS);
+                        Err(block.line.error(block.count_token, expected))
+                    } else {
+                        for _ in 0..block.count {
+                            parent.extend_from_slice(&block.commands);
+                        }
+                        line.finish(&mut tokens)
+                    }
+                }
+                None => Err(line.error(token, "a command")),
+            },
+            Some(direction) => parse_command(&line, direction, &mut tokens).map(|command| {
+                blocks
+                    .last_mut()
+                    .map_or(&mut commands, |b| &mut b.commands)
+                    .push(command)
+            }),
+        };
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    }
+
+    for block in blocks {
+        errors.push(ParseError::end_of_input(
+            input,
+            format!("`}}` closing the repeat of line {}", block.line.number),
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(commands)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Every error in `input`, in line order.
+pub fn validate(input: &str) -> Vec<ParseError> {
+    parse_program(input).err().unwrap_or_default()
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_program(input).map_err(|errors| errors.into_iter().next().unwrap())
+}
+
+/// How the submarine interprets commands.
+pub trait MovementModel {
+    /// Name the model is registered under
+    fn name(&self) -> &'static str;
+
+    fn apply(&self, position: &mut Position, command: Command);
+}
+
+/// Part 1's reading: `down` and `up` change the depth directly, and `back`
+/// undoes `forward`.
+pub struct Simple;
+
+impl MovementModel for Simple {
+    fn name(&self) -> &'static str {
+        "simple"
+    }
+
+    fn apply(&self, position: &mut Position, command: Command) {
+        match command {
+            Command::Forward(amount) => position.x += amount as i128,
+            Command::Back(amount) => position.x -= amount as i128,
+            Command::Down(amount) => position.y += amount as i128,
+            Command::Up(amount) => position.y -= amount as i128,
+        }
+    }
+}
+
+/// Part 2's reading: `down` and `up` change the aim, which `forward` follows
+/// and `back` follows in reverse.
+pub struct Aim;
+
+impl MovementModel for Aim {
+    fn name(&self) -> &'static str {
+        "aim"
+    }
+
+    fn apply(&self, position: &mut Position, command: Command) {
+        match command {
+            Command::Forward(amount) => {
+                position.x += amount as i128;
+                position.y += position.aim * amount as i128;
+            }
+            Command::Back(amount) => {
+                position.x -= amount as i128;
+                position.y -= position.aim * amount as i128;
+            }
+            Command::Down(amount) => position.aim += amount as i128,
+            Command::Up(amount) => position.aim -= amount as i128,
+        }
+    }
+}
+
+/// Movement models by name. The default registry holds the puzzle's two.
+pub struct Registry {
+    models: Vec<Box<dyn MovementModel>>,
+}
+
+impl Registry {
+    pub fn empty() -> Self {
+        Registry { models: Vec::new() }
+    }
+
+    /// Adds `model`, replacing any model registered under the same name.
+    pub fn register(&mut self, model: impl MovementModel + 'static) {
+        self.models.retain(|m| m.name() != model.name());
+        self.models.push(Box::new(model));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn MovementModel> {
+        self.models
+            .iter()
+            .find(|m| m.name() == name)
+            .map(|m| m.as_ref())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.models.iter().map(|m| m.name())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry::empty();
+        registry.register(Simple);
+        registry.register(Aim);
+        registry
+    }
+}
+
+/// Every position `steps` take the submarine through under `model`, from
+/// the surface to where it ends up.
+pub fn course<'a>(
+    model: &'a dyn MovementModel,
+    steps: &'a [Command],
+) -> impl Iterator<Item = Position> + 'a {
+    let start = Position::default();
+    std::iter::once(start).chain(steps.iter().scan(start, move |cursor, &step| {
+        model.apply(cursor, step);
+        Some(*cursor)
+    }))
+}
+
+/// Where `steps` take the submarine from the surface under `model`.
+pub fn navigate(model: &dyn MovementModel, steps: &[Command]) -> Position {
+    course(model, steps).last().unwrap()
+}
+
+// Distances and depths both stay below 2^55
+pub fn part1(steps: &[Command]) -> i128 {
+    let cursor = navigate(&Simple, steps);
+    cursor.x * cursor.y
+}
+
+/// The product of the distance and depth, unless it overflows.
+pub fn part2(steps: &[Command]) -> Option<i128> {
+    let cursor = navigate(&Aim, steps);
+    cursor.x.checked_mul(cursor.y)
+}
+
+// Highest aim `generate` lets the submarine take, which keeps the answers of
+// puzzle-sized inputs within an i32
+const MAX_AIM: i32 = 500;
+
+/// `size` random commands. The submarine never rises above the surface nor
+/// aims upwards.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut aim = 0;
+    let mut result = String::new();
+    for _ in 0..size {
+        let amount = rng.range(1..=9) as i32;
+        let command = match rng.below(3) {
+            0 => Command::Forward(amount),
+            1 if aim + amount <= MAX_AIM => Command::Down(amount),
+            _ if aim > 0 => Command::Up(amount.min(aim)),
+            _ => Command::Down(amount),
+        };
+        match command {
+            Command::Down(amount) => aim += amount,
+            Command::Up(amount) => aim -= amount,
+            _ => {}
+        }
+        result.push_str(&format!("{}\n", command));
+    }
+    result
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Command>;
+    type Answer1 = i128;
+    type Answer2 = Result<i128, &'static str>;
+
+    const GENERATE_SIZE: usize = 1000;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input).ok_or("the product of distance and depth overflows an i128")
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Aim model whose submarine cannot go deeper than a floor
+    struct Floored(i128);
+
+    impl MovementModel for Floored {
+        fn name(&self) -> &'static str {
+            "floored"
+        }
+
+        fn apply(&self, position: &mut Position, command: Command) {
+            Aim.apply(position, command);
+            position.y = position.y.min(self.0);
+        }
+    }
+
+    #[test]
+    fn registered_models_share_the_navigation_loop() {
+        let steps = parse_input("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n").unwrap();
+        let mut registry = Registry::default();
+        registry.register(Floored(30));
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["simple", "aim", "floored"]
+        );
+
+        let position = |name| navigate(registry.get(name).unwrap(), &steps);
+        assert_eq!(
+            position("simple"),
+            Position {
+                x: 15,
+                y: 10,
+                aim: 0
+            }
+        );
+        assert_eq!(
+            position("aim"),
+            Position {
+                x: 15,
+                y: 60,
+                aim: 10
+            }
+        );
+        assert_eq!(
+            position("floored"),
+            Position {
+                x: 15,
+                y: 30,
+                aim: 10
+            }
+        );
+        assert!(registry.get("drag").is_none());
+    }
+
+    #[test]
+    fn unrolls_repeats_and_skips_comments() {
+        let input = "# warm up\nforward 2\nrepeat 2 {\n  down 1  # dive\n  repeat 3 {\n    forward 1\n  }\n}\n\nback 4\n";
+        let steps = parse_input(input).unwrap();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(
+            &steps[..3],
+            [Command::Forward(2), Command::Down(1), Command::Forward(1)]
+        );
+        assert_eq!(steps[9], Command::Back(4));
+        assert_eq!(navigate(&Simple, &steps), Position { x: 4, y: 2, aim: 0 });
+        assert_eq!(navigate(&Aim, &steps), Position { x: 4, y: 1, aim: 2 });
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let input = "forward 1\nsideways 2\ndown -3\nrepeat x {\nup 1\n}\n}\nrepeat 2 {\n";
+        let errors = validate(input);
+        let found = errors
+            .iter()
+            .map(|e| (e.line, e.token.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [(2, "sideways"), (3, "-3"), (4, "x"), (7, "}"), (9, "")]
+        );
+        assert_eq!(
+            errors[0].expected,
+            "`forward`, `down`, `up`, `back`, `repeat` or `}`"
+        );
+        assert_eq!(errors[4].expected, "`}` closing the repeat of line 8");
+        assert!(validate("repeat 100000 {\nrepeat 1000 {\nup 1\n}\n}\n")[0]
+            .expected
+            .starts_with("a count"));
+    }
+
+    #[test]
+    fn keeps_long_courses_from_overflowing() {
+        let steps = parse_input("repeat 1000 {\n forward 10000000\n}\ndown 1\n").unwrap();
+        assert_eq!(part1(&steps), 10_000_000_000);
+        assert_eq!(part2(&steps), Some(0));
+
+        let steps = parse_input(&format!(
+            "repeat 3000000 {{\n down {0}\n forward {0}\n}}\n",
+            i32::MAX
+        ))
+        .unwrap();
+        let position = navigate(&Aim, &steps);
+        assert_eq!(position.x, 3_000_000 * i32::MAX as i128);
+        assert!(position.y > i64::MAX as i128);
+        assert_eq!(part2(&steps), None);
+    }
+}
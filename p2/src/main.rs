@@ -1,70 +1,111 @@
-use std::io::BufRead;
+use aoc_common::InputSource;
+use p2::trajectory::{self, Trajectory};
+use p2::{parse_program, Command, Registry};
+use std::process::exit;
 
-#[derive(Debug)]
-struct Position {
-    x: i32,
-    y: i32,
-    aim: i32,
-}
+const USAGE: &str = "Usage: p2 trajectory [--input <path>] [--model <name>]... [--format csv|svg]
+       p2 check [--input <path>]
 
-fn part1(steps: &[impl AsRef<str>]) {
-    let mut cursor = Position { x: 0, y: 0, aim: 0 };
+`trajectory` records every position the submarine goes through under each
+model (default: all of them) and prints them as CSV, or plots depth against
+distance as SVG.
 
-    for step in steps {
-        let mut token_iterator = step.as_ref().split_whitespace();
-        let direction = token_iterator.next().unwrap();
-        let amount = token_iterator.next().unwrap().parse::<i32>().unwrap();
-        match direction {
-            "forward" => {
-                cursor.x += amount;
-            }
-            "down" => {
-                cursor.y += amount;
-            }
-            "up" => {
-                cursor.y -= amount;
-            }
-            _ => panic!(),
+`check` reports every bad line of a course at once.";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
+}
+
+// Reads the course from `source`, reporting every bad line if any
+fn read_course(source: &InputSource) -> Vec<Command> {
+    let input = source.read().unwrap_or_else(|e| {
+        eprintln!("error: cannot read {}: {}", source.name(), e);
+        exit(1);
+    });
+    parse_program(&input).unwrap_or_else(|errors| {
+        for e in &errors {
+            eprint!("{}", e.render(&source.name(), &input));
         }
-    }
+        eprintln!("{} bad lines", errors.len());
+        exit(1);
+    })
+}
 
-    println!("{:?}", cursor);
-    println!("{}", cursor.x * cursor.y);
+fn check_main(args: &[String]) {
+    let source = match args {
+        [] => InputSource::bundled(2),
+        [option, path] if option == "--input" => InputSource::from_arg(path),
+        _ => usage_error("check only takes --input <path>"),
+    };
+    let steps = read_course(&source);
+    println!("{}: {} commands", source.name(), steps.len());
 }
 
-fn part2(steps: &[impl AsRef<str>]) {
-    let mut cursor = Position { x: 0, y: 0, aim: 0 };
+fn trajectory_main(args: &[String]) {
+    let registry = Registry::default();
+    let mut it = args.iter();
+    let mut source = InputSource::bundled(2);
+    let mut models = Vec::new();
+    let mut svg = false;
 
-    for step in steps {
-        let mut token_iterator = step.as_ref().split_whitespace();
-        let direction = token_iterator.next().unwrap();
-        let amount = token_iterator.next().unwrap().parse::<i32>().unwrap();
-        match direction {
-            "forward" => {
-                cursor.x += amount;
-                cursor.y += cursor.aim * amount;
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                source = InputSource::from_arg(path);
             }
-            "down" => {
-                cursor.aim += amount;
+            "--model" => {
+                let name = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--model expects a name"));
+                let model = registry.get(name).unwrap_or_else(|| {
+                    let known = registry.names().collect::<Vec<&str>>().join(", ");
+                    usage_error(&format!("unknown model '{}' (known: {})", name, known))
+                });
+                models.push(model);
             }
-            "up" => {
-                cursor.aim -= amount;
+            "--format" => {
+                svg = match it.next().map(|f| f.as_str()) {
+                    Some("csv") => false,
+                    Some("svg") => true,
+                    _ => usage_error("--format expects csv or svg"),
+                };
             }
-            _ => panic!(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
         }
     }
+    if models.is_empty() {
+        models = registry
+            .names()
+            .map(|name| registry.get(name).unwrap())
+            .collect();
+    }
 
-    println!("{:?}", cursor);
-    println!("{}", cursor.x * cursor.y);
+    let steps = read_course(&source);
+    let trajectories = models
+        .into_iter()
+        .map(|model| Trajectory::record(model, &steps))
+        .collect::<Vec<Trajectory>>();
+    if svg {
+        print!("{}", trajectory::to_svg(&trajectories));
+    } else {
+        print!("{}", trajectory::to_csv(&trajectories));
+    }
 }
 
 fn main() {
-    let steps = std::io::stdin()
-        .lock()
-        .lines()
-        .map(|res| res.unwrap())
-        .collect::<Vec<String>>();
-
-    part1(&steps);
-    part2(&steps);
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("trajectory") => trajectory_main(&args[1..]),
+        Some("check") => check_main(&args[1..]),
+        _ => aoc_common::main::<p2::Day2>(),
+    }
 }
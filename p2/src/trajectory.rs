@@ -0,0 +1,165 @@
+use crate::{course, Command, MovementModel, Position};
+
+/// Course of the submarine under one movement model.
+pub struct Trajectory {
+    pub model: &'static str,
+    /// Positions from the surface, one more than there are commands
+    pub positions: Vec<Position>,
+}
+
+impl Trajectory {
+    pub fn record(model: &dyn MovementModel, steps: &[Command]) -> Self {
+        Trajectory {
+            model: model.name(),
+            positions: course(model, steps).collect(),
+        }
+    }
+}
+
+/// One row per position of each trajectory.
+pub fn to_csv(trajectories: &[Trajectory]) -> String {
+    let mut result = "model,step,x,y,aim\n".to_string();
+    for trajectory in trajectories {
+        for (step, p) in trajectory.positions.iter().enumerate() {
+            result.push_str(&format!(
+                "{},{},{},{},{}\n",
+                trajectory.model, step, p.x, p.y, p.aim
+            ));
+        }
+    }
+    result
+}
+
+const PLOT_WIDTH: f64 = 800.0;
+const PANEL_HEIGHT: f64 = 240.0;
+const MARGIN: f64 = 60.0;
+const COLORS: &[&str] = &["#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e"];
+
+// Range of `values`, always including 0 and never empty
+fn bounds(values: impl Iterator<Item = i128>) -> (f64, f64) {
+    let (low, high) = values.fold((0, 0), |(low, high), v| (low.min(v), high.max(v)));
+    (low as f64, (high.max(low + 1)) as f64)
+}
+
+/// Plots depth against distance for each trajectory, one panel below the
+/// other. Panels share the distance axis but each has its own depth scale,
+/// as the models may reach very different depths. Depth grows downwards.
+pub fn to_svg(trajectories: &[Trajectory]) -> String {
+    let (x_low, x_high) = bounds(
+        trajectories
+            .iter()
+            .flat_map(|t| t.positions.iter().map(|p| p.x)),
+    );
+    let width = PLOT_WIDTH + 2.0 * MARGIN;
+    let height = trajectories.len().max(1) as f64 * (PANEL_HEIGHT + MARGIN) + MARGIN;
+    let scale_x = |x: i128| MARGIN + (x as f64 - x_low) / (x_high - x_low) * PLOT_WIDTH;
+
+    let mut result = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        width, height
+    );
+
+    for (i, trajectory) in trajectories.iter().enumerate() {
+        let top = MARGIN + i as f64 * (PANEL_HEIGHT + MARGIN);
+        let bottom = top + PANEL_HEIGHT;
+        let (y_low, y_high) = bounds(trajectory.positions.iter().map(|p| p.y));
+        let scale_y = |y: i128| top + (y as f64 - y_low) / (y_high - y_low) * PANEL_HEIGHT;
+        let color = COLORS[i % COLORS.len()];
+
+        result.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#999\"/>\n",
+            MARGIN, top, PLOT_WIDTH, PANEL_HEIGHT
+        ));
+        // Surface
+        result.push_str(&format!(
+            "<line x1=\"{0}\" y1=\"{1:.1}\" x2=\"{2}\" y2=\"{1:.1}\" stroke=\"#ccc\" stroke-dasharray=\"4 4\"/>\n",
+            MARGIN,
+            scale_y(0),
+            MARGIN + PLOT_WIDTH
+        ));
+        result.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-weight=\"bold\">{}</text>\n",
+            MARGIN,
+            top - 8.0,
+            color,
+            trajectory.model
+        ));
+        for (y, depth) in [(top + 4.0, y_low), (bottom, y_high)] {
+            result.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+                MARGIN - 6.0,
+                y,
+                depth
+            ));
+        }
+        result.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" transform=\"rotate(-90 {} {})\">depth</text>\n",
+            MARGIN - 30.0,
+            top + PANEL_HEIGHT / 2.0,
+            MARGIN - 30.0,
+            top + PANEL_HEIGHT / 2.0
+        ));
+
+        let points = trajectory
+            .positions
+            .iter()
+            .map(|p| format!("{:.1},{:.1}", scale_x(p.x), scale_y(p.y)))
+            .collect::<Vec<String>>()
+            .join(" ");
+        result.push_str(&format!(
+            "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>\n",
+            color, points
+        ));
+    }
+
+    let axis_y = height - MARGIN / 2.0;
+    for (x, label) in [(MARGIN, x_low), (MARGIN + PLOT_WIDTH, x_high)] {
+        result.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            x, axis_y, label
+        ));
+    }
+    result.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">distance</text>\n</svg>\n",
+        MARGIN + PLOT_WIDTH / 2.0,
+        axis_y
+    ));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Aim, Simple};
+
+    const SAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    #[test]
+    fn records_every_position() {
+        let steps = parse_input(SAMPLE).unwrap();
+        let trajectories = [
+            Trajectory::record(&Simple, &steps),
+            Trajectory::record(&Aim, &steps),
+        ];
+        assert_eq!(trajectories[1].positions.len(), 7);
+        assert_eq!(
+            trajectories[1].positions[3],
+            Position {
+                x: 13,
+                y: 40,
+                aim: 5
+            }
+        );
+
+        let csv = to_csv(&trajectories);
+        assert_eq!(csv.lines().count(), 15);
+        assert!(csv.starts_with("model,step,x,y,aim\nsimple,0,0,0,0\nsimple,1,5,0,0\n"));
+        assert!(csv.ends_with("aim,6,15,60,10\n"));
+
+        let svg = to_svg(&trajectories);
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,200 @@
+use aoc_common::{input_lines, Diagnostic, Grid, Param, Params, ParseError, Rng, Solution};
+
+const ALGORITHM_LEN: usize = 512;
+
+pub fn parse_input(input: &str) -> Result<(Vec<char>, Grid<char>), ParseError> {
+    let is_pixel = |c| c == '.' || c == '#';
+    let mut line_iter = input_lines(input);
+
+    let algorithm_line = line_iter
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "an enhancement algorithm"))?;
+    let algorithm = algorithm_line
+        .check_chars(is_pixel, "`.` or `#`")?
+        .chars()
+        .collect::<Vec<char>>();
+    if algorithm.len() < ALGORITHM_LEN {
+        return Err(algorithm_line.end_error(format!("{} pixels", ALGORITHM_LEN)));
+    }
+    if algorithm.len() > ALGORITHM_LEN {
+        return Err(algorithm_line.char_error(ALGORITHM_LEN, "end of line"));
+    }
+
+    line_iter
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "an empty line"))?
+        .blank()?;
+
+    let image = Grid::parse_lines(line_iter, "`.` or `#`", |c| {
+        Some(c).filter(|&c| is_pixel(c))
+    })?;
+    if image.height() == 0 {
+        return Err(ParseError::end_of_input(input, "an image"));
+    }
+
+    Ok((algorithm, image))
+}
+
+/// Applies the enhancement algorithm `steps` times, returning the resulting
+/// image with its padding.
+pub fn enhance(algorithm: &[char], image: &Grid<char>, steps: usize) -> Grid<char> {
+    // Add steps+2 elements for padding in each of the 4 edges of the image
+    let padding = steps + 2;
+    let rows = image.height() + 2 * padding;
+    let cols = image.width() + 2 * padding;
+    let mut input_image = Grid::from_fn(cols, rows, |(i, j)| {
+        if i < padding || j < padding {
+            '.'
+        } else {
+            *image.get((i - padding, j - padding)).unwrap_or(&'.')
+        }
+    });
+
+    for _ in 0..steps {
+        input_image = Grid::from_fn(cols, rows, |(i, j)| {
+            if i == 0 || i == rows - 1 || j == 0 || j == cols - 1 {
+                return match input_image[(i, j)] {
+                    '.' => algorithm[0],
+                    '#' => algorithm[511],
+                    _ => panic!(),
+                };
+            }
+
+            let binary_str = (i - 1..=i + 1)
+                .flat_map(|row| &input_image.row(row)[j - 1..=j + 1])
+                .map(|&c| match c {
+                    '.' => '0',
+                    '#' => '1',
+                    _ => panic!(),
+                })
+                .collect::<String>();
+            let index = usize::from_str_radix(&binary_str, 2).unwrap();
+            algorithm[index]
+        });
+    }
+
+    input_image
+}
+
+/// Number of pixels lit after `steps` enhancements, or `None` if the
+/// infinite background around the image is lit too.
+pub fn solve(algorithm: &[char], image: &Grid<char>, steps: usize) -> Option<usize> {
+    lit(&enhance(algorithm, image, steps))
+}
+
+// Lit pixels of an enhanced image, whose padding stands for the background
+fn lit(image: &Grid<char>) -> Option<usize> {
+    if image[(0, 0)] == '#' {
+        return None;
+    }
+    Some(image.iter().filter(|&&c| c == '#').count())
+}
+
+const INFINITELY_MANY: &str = "infinitely many pixels are lit";
+
+const PART1_STEPS: usize = 2;
+const PART2_STEPS: usize = 50;
+
+pub fn part1(algorithm: &[char], input_image: &Grid<char>, steps: usize) -> Option<usize> {
+    solve(algorithm, input_image, steps)
+}
+
+pub fn part2(algorithm: &[char], input_image: &Grid<char>, steps: usize) -> Option<usize> {
+    solve(algorithm, input_image, steps)
+}
+
+// Steps for `part`, unless given on the command line
+fn steps(input: &<Day20 as Solution>::Input, part: u8) -> usize {
+    let default = if part == 1 { PART1_STEPS } else { PART2_STEPS };
+    input.2.unwrap_or(default)
+}
+
+/// A random enhancement algorithm and `size` x `size` input image. An
+/// algorithm lighting up dark regions always turns lit ones dark again, so
+/// the number of lit pixels stays finite.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pixel = |rng: &mut Rng| if rng.chance(1, 2) { '#' } else { '.' };
+    let mut algorithm = (0..ALGORITHM_LEN)
+        .map(|_| pixel(rng))
+        .collect::<Vec<char>>();
+    if algorithm[0] == '#' {
+        algorithm[ALGORITHM_LEN - 1] = '.';
+    }
+    let image = Grid::from_fn(size, size, |_| pixel(rng));
+    format!("{}\n\n{}", algorithm.into_iter().collect::<String>(), image)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+
+    // Algorithm, image, and the number of steps if given
+    type Input = (Vec<char>, Grid<char>, Option<usize>);
+    type Answer1 = Result<usize, &'static str>;
+    type Answer2 = Result<usize, &'static str>;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        help: "times the image is enhanced (default: 2 for part 1, 50 for part 2)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let (algorithm, image) = parse_input(input)?;
+        Ok((algorithm, image, params.get("steps")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.1, steps(input, 1)).ok_or(INFINITELY_MANY)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, &input.1, steps(input, 2)).ok_or(INFINITELY_MANY)
+    }
+
+    fn diagnostics(input: &Self::Input, part: u8) -> Vec<Diagnostic> {
+        let image = enhance(&input.0, &input.1, steps(input, part));
+        vec![
+            Diagnostic::new("width", image.width()),
+            Diagnostic::new("height", image.height()),
+            Diagnostic::new("lit", lit(&image).ok_or(INFINITELY_MANY)),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_none_when_the_background_is_lit() {
+        // Dark regions light up and lit ones go dark again
+        let mut algorithm = ".".repeat(ALGORITHM_LEN);
+        algorithm.replace_range(0..1, "#");
+        let input = format!("{}\n\n#..\n.#.\n..#\n", algorithm);
+        let (algorithm, image) = parse_input(&input).unwrap();
+        assert_eq!(solve(&algorithm, &image, 1), None);
+        assert_eq!(solve(&algorithm, &image, 2), Some(3));
+        assert_eq!(solve(&algorithm, &image, 3), None);
+
+        let mut params = Params::default();
+        params.set("steps", 1);
+        let input = Day20::parse_with(&input, &params).unwrap();
+        let lit = Day20::diagnostics(&input, 1).pop().unwrap();
+        assert_eq!(
+            lit.value.to_string(),
+            "none (infinitely many pixels are lit)"
+        );
+    }
+}
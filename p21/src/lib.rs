@@ -0,0 +1,304 @@
+use aoc_common::{input_lines, Line, Param, Params, ParseError, Rng, Solution};
+
+fn parse_player(input: &str, line: Option<Line>, player: &str) -> Result<usize, ParseError> {
+    let line = line.ok_or_else(|| ParseError::end_of_input(input, "a starting position"))?;
+    let mut tokenizer = line.text.split_whitespace();
+    line.literal(&mut tokenizer, "Player")?;
+    line.literal(&mut tokenizer, player)?;
+    line.literal(&mut tokenizer, "starting")?;
+    line.literal(&mut tokenizer, "position:")?;
+    let position_token = line.next(&mut tokenizer, "a position")?;
+    let position = line.parse::<usize>(position_token, "a position")?;
+    if !(1..=10).contains(&position) {
+        return Err(line.error(position_token, "a position between 1 and 10"));
+    }
+    line.finish(&mut tokenizer)?;
+    Ok(position)
+}
+
+pub fn parse_input(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut lines_iterator = input_lines(input);
+    let p1 = parse_player(input, lines_iterator.next(), "1")?;
+    let p2 = parse_player(input, lines_iterator.next(), "2")?;
+
+    Ok((p1, p2))
+}
+
+pub mod part1 {
+    use std::iter::Cycle;
+    use std::ops::RangeInclusive;
+    struct DeterministicDie(Cycle<RangeInclusive<usize>>);
+
+    pub const VICTORY_SCORE: u64 = 1000;
+
+    impl DeterministicDie {
+        fn new() -> Self {
+            DeterministicDie((1..=100).cycle())
+        }
+
+        fn roll(&mut self) -> usize {
+            self.0.next().unwrap()
+        }
+    }
+
+    // After this many turns the die has gone round 30 times and each pawn
+    // has moved ten times as far as in its first 50 turns, back to where it
+    // started: every such period scores the same.
+    const PERIOD: usize = 1000;
+
+    /// Number of rolls times the score of the losing player, if it fits in
+    /// a u128. Games to high scores skip over whole periods rather than
+    /// playing them out.
+    pub fn solve(p1: usize, p2: usize, victory_score: u64) -> Option<u128> {
+        let victory_score = victory_score as u128;
+        let mut die = DeterministicDie::new();
+        let mut positions = [p1 - 1, p2 - 1];
+        let mut scores = [0u128; 2];
+        let mut num_rolls = 0u128;
+
+        for turn in 1.. {
+            let player = (turn - 1) % 2;
+            let steps = die.roll() + die.roll() + die.roll();
+            num_rolls += 3;
+            positions[player] = (positions[player] + steps) % 10;
+            scores[player] += positions[player] as u128 + 1;
+
+            if scores[player] >= victory_score {
+                return num_rolls.checked_mul(scores[1 - player]);
+            }
+
+            if turn == PERIOD {
+                // As many periods as leave both players short of winning
+                let periods = scores
+                    .iter()
+                    .map(|&score| (victory_score - 1) / score)
+                    .min()
+                    .unwrap();
+                scores = scores.map(|score| score * periods);
+                num_rolls *= periods;
+            }
+        }
+        unreachable!()
+    }
+}
+
+pub mod part2 {
+    use itertools::Itertools;
+    use std::collections::HashMap;
+
+    type DynProg = HashMap<(usize, usize, u64, u64), (u128, u128)>;
+
+    pub const VICTORY_SCORE: u64 = 21;
+
+    // No player scores more than 10 a turn, so games to a higher score last
+    // at least 29 turns, each splitting the universe in 27. One of the
+    // players then wins in more universes than a u128 can count.
+    const MAX_VICTORY_SCORE: u64 = 140;
+
+    fn calc(
+        p1: usize,
+        p2: usize,
+        score_p1: u64,
+        score_p2: u64,
+        victory_score: u64,
+        possible_steps: &[(usize, u128)],
+        d: &mut DynProg,
+    ) -> Option<(u128, u128)> {
+        let tuple = (p1, p2, score_p1, score_p2);
+        if let Some(&value) = d.get(&tuple) {
+            return Some(value);
+        }
+
+        let mut subproblems_sum = (0, 0);
+
+        // Adds wins in `universes` universes, unless they overflow
+        let add = |(w1, w2): (u128, u128), (v1, v2): (u128, u128), universes: u128| {
+            Some((
+                w1.checked_add(v1.checked_mul(universes)?)?,
+                w2.checked_add(v2.checked_mul(universes)?)?,
+            ))
+        };
+
+        for &(step_p1, universes_p1) in possible_steps {
+            let new_p1 = (p1 + step_p1) % 10;
+            let new_score_p1 = score_p1 + new_p1 as u64 + 1;
+
+            if new_score_p1 >= victory_score {
+                subproblems_sum = add(subproblems_sum, (1, 0), universes_p1)?;
+                continue;
+            }
+
+            for &(step_p2, universes_p2) in possible_steps {
+                let new_p2 = (p2 + step_p2) % 10;
+                let new_score_p2 = score_p2 + new_p2 as u64 + 1;
+
+                let wins = if new_score_p2 >= victory_score {
+                    (0, 1)
+                } else {
+                    calc(
+                        new_p1,
+                        new_p2,
+                        new_score_p1,
+                        new_score_p2,
+                        victory_score,
+                        possible_steps,
+                        d,
+                    )?
+                };
+                subproblems_sum = add(subproblems_sum, wins, universes_p1 * universes_p2)?;
+            }
+        }
+
+        d.insert(tuple, subproblems_sum);
+
+        Some(subproblems_sum)
+    }
+
+    /// Universes in which the player winning the most wins, if they fit in
+    /// a u128.
+    pub fn solve(p1: usize, p2: usize, victory_score: u64) -> Option<u128> {
+        if victory_score > MAX_VICTORY_SCORE {
+            return None;
+        }
+        // Sums of three rolls, with the number of universes rolling them
+        let possible_steps = (1..=3)
+            .cartesian_product(1..=3)
+            .cartesian_product(1..=3)
+            .map(|((x, y), z)| x + y + z)
+            .counts()
+            .into_iter()
+            .map(|(step, universes)| (step, universes as u128))
+            .collect::<Vec<(usize, u128)>>();
+        let mut d = DynProg::new();
+        let (p1_wins, p2_wins) =
+            calc(p1 - 1, p2 - 1, 0, 0, victory_score, &possible_steps, &mut d)?;
+        Some(std::cmp::max(p1_wins, p2_wins))
+    }
+}
+
+/// Random starting positions for both players. `size` is unused, as the game
+/// always has two players.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+        rng.range(1..=10),
+        rng.range(1..=10)
+    )
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+
+    // Starting positions, and the victory scores of each part if given
+    type Input = (usize, usize, Option<u64>, Option<u64>);
+    type Answer1 = Result<u128, &'static str>;
+    type Answer2 = Result<u128, &'static str>;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "victory-score",
+            help: "score a player needs to win (default: 1000 for part 1, 21 for part 2)",
+            values: &[],
+        },
+        Param {
+            name: "deterministic-score",
+            help: "overrides --victory-score with the deterministic die (part 1)",
+            values: &[],
+        },
+        Param {
+            name: "dirac-score",
+            help: "overrides --victory-score with the Dirac die (part 2)",
+            values: &[],
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let (p1, p2) = parse_input(input)?;
+        let score = |name| {
+            params
+                .get(name)
+                .or(params.get("victory-score"))
+                .map(|s| s as u64)
+        };
+        Ok((p1, p2, score("deterministic-score"), score("dirac-score")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::solve(input.0, input.1, input.2.unwrap_or(part1::VICTORY_SCORE))
+            .ok_or("the answer overflows a u128")
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::solve(input.0, input.1, input.3.unwrap_or(part2::VICTORY_SCORE))
+            .ok_or("the number of universes overflows a u128")
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Plays the deterministic game out turn by turn
+    fn play(mut positions: [u128; 2], victory_score: u128) -> u128 {
+        let mut scores = [0; 2];
+        let mut rolls = 0;
+        for player in [0, 1].into_iter().cycle() {
+            let steps = (rolls..rolls + 3).map(|roll| roll % 100 + 1).sum::<u128>();
+            rolls += 3;
+            positions[player] = (positions[player] + steps - 1) % 10 + 1;
+            scores[player] += positions[player];
+            if scores[player] >= victory_score {
+                return rolls * scores[1 - player];
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn skips_periods_of_long_deterministic_games() {
+        assert_eq!(part1::solve(4, 8, 1000), Some(739785));
+        for (p1, p2) in [(4, 8), (1, 1), (10, 3)] {
+            for score in [1, 1000, 5500, 12345, 1_000_000] {
+                let expected = play([p1 as u128, p2 as u128], score as u128);
+                assert_eq!(part1::solve(p1, p2, score), Some(expected));
+            }
+        }
+        assert!(part1::solve(4, 8, 5_000_000_000).is_some_and(|answer| answer > u64::MAX as u128));
+        assert_eq!(part1::solve(2, 8, u64::MAX), None);
+    }
+
+    #[test]
+    fn overrides_the_victory_score_per_part() {
+        let input = "Player 1 starting position: 4\nPlayer 2 starting position: 8\n";
+        let mut params = Params::default();
+        assert_eq!(Day21::parse_with(input, &params), Ok((4, 8, None, None)));
+        params.set("victory-score", 10);
+        assert_eq!(
+            Day21::parse_with(input, &params),
+            Ok((4, 8, Some(10), Some(10)))
+        );
+        params.set("dirac-score", 15);
+        assert_eq!(
+            Day21::parse_with(input, &params),
+            Ok((4, 8, Some(10), Some(15)))
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(part2::solve(4, 8, 21), Some(444356092776315));
+        assert!(part2::solve(4, 8, 30).is_some_and(|wins| wins > u64::MAX as u128));
+        assert_eq!(part2::solve(4, 8, 58), None);
+        assert_eq!(part2::solve(4, 8, 1_000_000), None);
+    }
+}
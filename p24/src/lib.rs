@@ -0,0 +1,449 @@
+use aoc_common::{input_lines, Diagnostic, Line, Params, ParseError, Rng, Solution};
+
+#[derive(Debug)]
+struct Machine {
+    registers: [i64; 4],
+}
+
+impl Machine {
+    fn new() -> Machine {
+        Machine { registers: [0; 4] }
+    }
+
+    fn set_reg(&mut self, idx: usize, val: i64) {
+        self.registers[idx] = val;
+    }
+
+    fn get_reg(&self, idx: usize) -> i64 {
+        self.registers[idx]
+    }
+}
+
+pub enum Operand {
+    Register(usize),
+    Immediate(i64),
+}
+
+pub enum BinaryOp {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+pub enum Instruction {
+    Inp(usize),
+    Binary(BinaryOp, usize, Operand),
+}
+
+const REGISTER_Z: usize = 3;
+
+fn parse_register_idx(line: &Line, s: &str) -> Result<usize, ParseError> {
+    match s {
+        "w" => Ok(0),
+        "x" => Ok(1),
+        "y" => Ok(2),
+        "z" => Ok(REGISTER_Z),
+        _ => Err(line.error(s, "a register")),
+    }
+}
+
+fn parse_operand<'a>(
+    line: &Line<'a>,
+    it: &mut impl Iterator<Item = &'a str>,
+) -> Result<Operand, ParseError> {
+    let s = line.next(it, "a register or an integer")?;
+    if let Ok(imm) = s.parse::<i64>() {
+        Ok(Operand::Immediate(imm))
+    } else {
+        parse_register_idx(line, s)
+            .map(Operand::Register)
+            .map_err(|_| line.error(s, "a register or an integer"))
+    }
+}
+
+fn parse_register<'a>(
+    line: &Line<'a>,
+    it: &mut impl Iterator<Item = &'a str>,
+) -> Result<usize, ParseError> {
+    let s = line.next(it, "a register")?;
+    parse_register_idx(line, s)
+}
+
+fn parse_instruction(line: &Line) -> Result<Instruction, ParseError> {
+    let mut tokenizer = line.text.split_whitespace();
+    let mnemonic = line.next(&mut tokenizer, "an instruction")?;
+    let operator = match mnemonic {
+        "inp" => {
+            let reg = parse_register(line, &mut tokenizer)?;
+            line.finish(&mut tokenizer)?;
+            return Ok(Instruction::Inp(reg));
+        }
+        "add" => BinaryOp::Add,
+        "mul" => BinaryOp::Mul,
+        "div" => BinaryOp::Div,
+        "mod" => BinaryOp::Mod,
+        "eql" => BinaryOp::Eql,
+        _ => return Err(line.error(mnemonic, "an instruction")),
+    };
+    let dest_reg = parse_register(line, &mut tokenizer)?;
+    let operand = parse_operand(line, &mut tokenizer)?;
+    line.finish(&mut tokenizer)?;
+
+    Ok(Instruction::Binary(operator, dest_reg, operand))
+}
+
+fn parse_digit(it: &mut impl Iterator<Item = char>) -> i64 {
+    it.next().unwrap() as i64 - '0' as i64
+}
+
+fn binary_op(
+    machine: &mut Machine,
+    dest_reg: usize,
+    operand: &Operand,
+    operator: &BinaryOp,
+) -> Result<(), ()> {
+    let op1 = machine.get_reg(dest_reg);
+    let op2 = match *operand {
+        Operand::Register(idx) => machine.get_reg(idx),
+        Operand::Immediate(val) => val,
+    };
+    let result = match operator {
+        BinaryOp::Add => op1.checked_add(op2).ok_or(())?,
+        BinaryOp::Mul => op1.checked_mul(op2).ok_or(())?,
+        BinaryOp::Div => op1.checked_div(op2).ok_or(())?,
+        BinaryOp::Mod => op1.checked_rem(op2).ok_or(())?,
+        BinaryOp::Eql => {
+            if op1 == op2 {
+                1
+            } else {
+                0
+            }
+        }
+    };
+    machine.set_reg(dest_reg, result);
+    Ok(())
+}
+
+fn eval(
+    machine: &mut Machine,
+    inst: &Instruction,
+    mut input_stream: impl Iterator<Item = char>,
+) -> Result<(), ()> {
+    match inst {
+        Instruction::Inp(reg) => {
+            let imm = parse_digit(&mut input_stream);
+            machine.set_reg(*reg, imm);
+        }
+        Instruction::Binary(operator, dest_reg, operand) => {
+            binary_op(machine, *dest_reg, operand, operator)?
+        }
+    }
+
+    Ok(())
+}
+
+// Final registers after running `program` on `number`, if it runs to the end
+fn run_program(program: &[Instruction], number: &str) -> Option<Machine> {
+    let mut machine = Machine::new();
+    let mut digit_iter = number.chars();
+
+    for inst in program {
+        if eval(&mut machine, inst, &mut digit_iter).is_err() {
+            return None;
+        }
+    }
+
+    Some(machine)
+}
+
+fn is_valid_model_number(program: &[Instruction], number: &str) -> bool {
+    run_program(program, number).is_some_and(|machine| machine.get_reg(REGISTER_Z) == 0)
+}
+
+// MONAD is made of 14 blocks of 18 instructions, one per input digit, which
+// only differ in three immediates: the divisor applied to z, the offset added
+// to x and the offset added to y. Blocks dividing z by 1 push `w + y_offset`
+// onto a base 26 stack kept in z. Blocks dividing by 26 pop it back, and only
+// refrain from pushing again when their digit equals the popped value plus
+// their x offset. z ends as zero when every pop is matched, so each popping
+// digit is pinned to the digit of the block that pushed.
+const BLOCK_LEN: usize = 18;
+
+// Instructions of every block, `_` standing for the immediate of the divisor
+// and the x and y offsets
+const BLOCK_TEMPLATE: [&str; BLOCK_LEN] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y _",
+    "mul y x", "add z y",
+];
+
+// Model numbers must fit in an i64
+const MAX_BLOCKS: usize = 18;
+
+struct Block {
+    divisor: i64,
+    x_offset: i64,
+    y_offset: i64,
+}
+
+/// A program shaped like MONAD, whose model numbers can be worked out
+/// without running it.
+pub struct Monad {
+    program: Vec<Instruction>,
+    blocks: Vec<Block>,
+}
+
+// Checks that `line` is instruction `offset` of a block, returning the
+// immediate the template leaves open, if any, with its token
+fn check_block_line<'a>(
+    line: &Line<'a>,
+    offset: usize,
+) -> Result<Option<(i64, &'a str)>, ParseError> {
+    let expected = BLOCK_TEMPLATE[offset];
+    let mut tokens = line.text.split_whitespace();
+    let mut immediate = None;
+    for word in expected.split(' ') {
+        if word == "_" {
+            let token = line.next(&mut tokens, "an integer")?;
+            immediate = Some((line.parse(token, "an integer")?, token));
+        } else {
+            let what = format!("`{}`, as in MONAD", expected);
+            let token = line.next(&mut tokens, &what)?;
+            if token != word {
+                return Err(line.error(token, what));
+            }
+        }
+    }
+    line.finish(&mut tokens)?;
+    Ok(immediate)
+}
+
+// Checks that the program is made of MONAD blocks whose pushes and pops
+// match up, with digits that can satisfy every pop
+fn parse_blocks(input: &str) -> Result<Vec<Block>, ParseError> {
+    let lines = input_lines(input).collect::<Vec<Line>>();
+    if lines.is_empty() {
+        return Err(ParseError::end_of_input(input, "`inp w`, as in MONAD"));
+    }
+
+    let mut blocks = Vec::new();
+    // y offsets of the blocks pushed and not popped yet
+    let mut stack = Vec::new();
+    for (i, lines) in lines.chunks(BLOCK_LEN).enumerate() {
+        if i == MAX_BLOCKS {
+            return Err(lines[0].error(lines[0].text, "end of input after 18 digits"));
+        }
+        let mut immediates = Vec::new();
+        for (offset, line) in lines.iter().enumerate() {
+            immediates.extend(check_block_line(line, offset)?);
+        }
+        if lines.len() < BLOCK_LEN {
+            let next = BLOCK_TEMPLATE[lines.len()];
+            return Err(ParseError::end_of_input(
+                input,
+                format!("`{}`, as in MONAD", next),
+            ));
+        }
+        let [(divisor, divisor_token), (x_offset, x_token), (y_offset, y_token)] = immediates[..]
+        else {
+            unreachable!("the template has three immediates");
+        };
+
+        match divisor {
+            1 => {
+                // Pushing blocks must never match their digit, and push a
+                // value that fits in a base 26 digit
+                if x_offset < 10 {
+                    return Err(lines[5].error(x_token, "an x offset of at least 10"));
+                }
+                if !(-1..=16).contains(&y_offset) {
+                    return Err(lines[15].error(y_token, "a y offset from -1 to 16"));
+                }
+                stack.push(y_offset);
+            }
+            26 => {
+                let Some(pushed) = stack.pop() else {
+                    return Err(lines[4].error(divisor_token, "1, as no block is left to pop"));
+                };
+                // The two digits differ by that much, which must leave room
+                // for both between 1 and 9
+                let diff = pushed.checked_add(x_offset);
+                if diff.is_none_or(|diff| !(-8..=8).contains(&diff)) {
+                    return Err(lines[5].error(
+                        x_token,
+                        format!("an x offset from {} to {}", -8 - pushed, 8 - pushed),
+                    ));
+                }
+            }
+            _ => return Err(lines[4].error(divisor_token, "1 or 26")),
+        }
+        blocks.push(Block {
+            divisor,
+            x_offset,
+            y_offset,
+        });
+    }
+    if !stack.is_empty() {
+        return Err(ParseError::end_of_input(
+            input,
+            format!("{} more blocks dividing z by 26", stack.len()),
+        ));
+    }
+    Ok(blocks)
+}
+
+fn find_model_number(monad: &Monad, largest: bool) -> i64 {
+    let mut digits = vec![0; monad.blocks.len()];
+    let mut stack = Vec::new();
+
+    for (i, block) in monad.blocks.iter().enumerate() {
+        if block.divisor == 1 {
+            stack.push((i, block.y_offset));
+        } else if let Some((j, y_offset)) = stack.pop() {
+            // digits[i] == digits[j] + diff
+            let diff = y_offset + block.x_offset;
+            digits[j] = if largest {
+                std::cmp::min(9, 9 - diff)
+            } else {
+                std::cmp::max(1, 1 - diff)
+            };
+            digits[i] = digits[j] + diff;
+        }
+    }
+
+    let number = digits.iter().fold(0, |acc, d| acc * 10 + d);
+    debug_assert!(is_valid_model_number(&monad.program, &number.to_string()));
+    number
+}
+
+pub fn parse_input(input: &str) -> Result<Monad, ParseError> {
+    let program = input_lines(input)
+        .map(|line| parse_instruction(&line))
+        .collect::<Result<Vec<Instruction>, ParseError>>()?;
+    let blocks = parse_blocks(input)?;
+    Ok(Monad { program, blocks })
+}
+
+pub fn part1(monad: &Monad) -> i64 {
+    find_model_number(monad, true)
+}
+
+pub fn part2(monad: &Monad) -> i64 {
+    find_model_number(monad, false)
+}
+
+fn monad_block(divisor: i64, x_offset: i64, y_offset: i64) -> String {
+    format!(
+        "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+         mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\n\
+         mul y x\nadd z y\n",
+        divisor, x_offset, y_offset
+    )
+}
+
+/// A random MONAD checking model numbers of `size` digits, rounded down to
+/// an even number from 2 to 18 so that every push has its pop and model
+/// numbers fit in an i64.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let digits = size.clamp(2, 18) / 2 * 2;
+    let mut pushes_left = digits / 2;
+    let mut pushed = Vec::new();
+    let mut result = String::new();
+    for _ in 0..digits {
+        if pushes_left > 0 && (pushed.is_empty() || rng.chance(1, 2)) {
+            // x offsets above 9 never match a digit, so these blocks always push
+            pushes_left -= 1;
+            let y_offset = rng.range(1..=16);
+            pushed.push(y_offset);
+            result.push_str(&monad_block(1, rng.range(10..=15), y_offset));
+        } else {
+            // This block's digit must be the pushing block's plus `diff`
+            let diff = rng.range(-8..=8);
+            let x_offset = diff - pushed.pop().unwrap();
+            result.push_str(&monad_block(26, x_offset, rng.range(1..=16)));
+        }
+    }
+    result
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    type Input = Monad;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const GENERATE_SIZE: usize = 14;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn diagnostics(input: &Self::Input, part: u8) -> Vec<Diagnostic> {
+        let number = find_model_number(input, part == 1);
+        run_program(&input.program, &number.to_string())
+            .into_iter()
+            .flat_map(|machine| ["w", "x", "y", "z"].into_iter().zip(machine.registers))
+            .map(|(name, value)| Diagnostic::new(name, value))
+            .collect()
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> (usize, String) {
+        match parse_input(input) {
+            Ok(_) => panic!("{:?} parsed", input),
+            Err(error) => (error.line, error.token),
+        }
+    }
+
+    #[test]
+    fn solves_generated_monads() {
+        let mut rng = Rng::new(24);
+        for size in [2, 14, 18] {
+            let monad = parse_input(&generate(&mut rng, size)).unwrap();
+            for number in [part1(&monad), part2(&monad)] {
+                assert_eq!(number.to_string().len(), size);
+                assert!(is_valid_model_number(&monad.program, &number.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_programs_not_shaped_like_monad() {
+        assert_eq!(parse_error(""), (1, String::new()));
+        assert_eq!(parse_error("inp w\nadd x 1\n"), (2, "add".to_string()));
+        assert_eq!(parse_error("inp w\nmul x 0\n"), (3, String::new()));
+        // Pops with nothing pushed, pushes never popped
+        assert_eq!(parse_error(&monad_block(26, 0, 1)), (5, "26".to_string()));
+        assert_eq!(parse_error(&monad_block(1, 10, 1)), (19, String::new()));
+        assert_eq!(parse_error(&monad_block(2, 10, 1)), (5, "2".to_string()));
+        // Digits would have to differ by 9
+        let program = monad_block(1, 10, 1) + &monad_block(26, 8, 1);
+        assert_eq!(parse_error(&program), (24, "8".to_string()));
+        let program = monad_block(1, 10, 1) + &monad_block(26, i64::MAX, 1);
+        assert_eq!(parse_error(&program), (24, i64::MAX.to_string()));
+        // 20 digits overflow an i64
+        let program = monad_block(1, 10, 1).repeat(10) + &monad_block(26, -1, 1).repeat(10);
+        assert_eq!(parse_error(&program), (18 * 18 + 1, "inp w".to_string()));
+    }
+}
@@ -0,0 +1,122 @@
+use aoc_common::{Diagnostic, Params, ParseError, Rng, Solution};
+
+pub mod number;
+pub mod query;
+pub mod report;
+pub mod trie;
+
+use number::Number;
+use report::Report;
+use trie::Trie;
+
+pub fn parse_input(input: &str) -> Result<Report, ParseError> {
+    report::parse_report(input)
+}
+
+/// Gamma and epsilon rates.
+pub fn rates(report: &Report) -> (Number, Number) {
+    let width = report.width();
+    let mut gamma_rate = vec![0u64; width.div_ceil(64)];
+    let mut echo_rate = gamma_rate.clone();
+
+    for (column, ones) in report.column_counts().into_iter().enumerate() {
+        let index = width - 1 - column;
+        // Strictly more ones than zeros
+        let rate = if 2 * ones > report.len() {
+            &mut gamma_rate
+        } else {
+            &mut echo_rate
+        };
+        rate[index / 64] |= 1 << (index % 64);
+    }
+
+    (
+        Number::from_limbs(&gamma_rate),
+        Number::from_limbs(&echo_rate),
+    )
+}
+
+pub fn part1(report: &Report) -> Number {
+    let (gamma_rate, echo_rate) = rates(report);
+    &gamma_rate * &echo_rate
+}
+
+/// Lines holding the oxygen generator and CO2 scrubber ratings. Parsed
+/// reports always have some.
+pub fn ratings(report: &Report) -> (usize, usize) {
+    let trie = Trie::new(report);
+    let oxygen_rate = trie.find(trie::OXYGEN).expect("empty report");
+    let co2_rate = trie.find(trie::CO2).expect("empty report");
+
+    (oxygen_rate, co2_rate)
+}
+
+pub fn part2(report: &Report) -> Number {
+    let (oxygen_rate, co2_rate) = ratings(report);
+
+    let oxygen_rate = Number::from_limbs(report.line(oxygen_rate));
+    let co2_rate = Number::from_limbs(report.line(co2_rate));
+
+    &oxygen_rate * &co2_rate
+}
+
+/// `size` distinct report lines, as wide as the puzzle's 12 bits unless more
+/// are needed to keep them distinct, up to 15 bits and so 32768 lines.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = (12..15).find(|&w| size <= 1 << w).unwrap_or(15);
+    let mut lines = (0..1usize << width).collect::<Vec<usize>>();
+    rng.shuffle(&mut lines);
+    lines
+        .iter()
+        .take(size)
+        .map(|line| format!("{:0width$b}\n", line, width = width))
+        .collect()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Report;
+    type Answer1 = Number;
+    type Answer2 = Number;
+
+    const GENERATE_SIZE: usize = 1000;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn diagnostics(input: &Self::Input, part: u8) -> Vec<Diagnostic> {
+        let (first, second) = match part {
+            1 => {
+                let (gamma_rate, echo_rate) = rates(input);
+                (
+                    Diagnostic::new("gamma_rate", gamma_rate),
+                    Diagnostic::new("epsilon_rate", echo_rate),
+                )
+            }
+            _ => {
+                let (oxygen_rate, co2_rate) = ratings(input);
+                (
+                    Diagnostic::new("oxygen_rating", Number::from_limbs(input.line(oxygen_rate))),
+                    Diagnostic::new("co2_rating", Number::from_limbs(input.line(co2_rate))),
+                )
+            }
+        };
+        vec![first, second]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
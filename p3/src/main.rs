@@ -1,122 +1,92 @@
-use std::io::BufRead;
+use aoc_common::{InputSource, Params};
+use p3::query::Query;
+use std::process::exit;
 
-fn bin_to_dec(s: &str) -> i32 {
-    s.chars().rev().enumerate().fold(0, |acc, (pos, i)| {
-        acc + (1 << pos) * (i as i32 - '0' as i32)
-    })
-}
-
-fn part1(l: &[String]) {
-    let line_len = l[0].len();
-    let mut gamma_rate = String::with_capacity(line_len);
-
-    for pos in 0..line_len {
-        let mut counter = 0;
-        for line in l.iter() {
-            let bit = line.chars().nth(pos).unwrap();
-            if bit == '1' {
-                counter += 1;
-            } else {
-                counter -= 1;
-            }
-        }
-        if counter > 0 {
-            gamma_rate.push('1');
-        } else {
-            gamma_rate.push('0');
-        }
-    }
-
-    let echo_rate = gamma_rate
-        .chars()
-        .map(|c| if c == '1' { '0' } else { '1' })
-        .collect::<String>();
+const USAGE: &str = "Usage: p3 query [--input <path>] [--min-match K]
 
-    println!("Gamma rate: {}", gamma_rate);
-    println!("Echo rate: {}", echo_rate);
+`query` prints how many lines have each bit in every column and the entropy
+of each column, then the line closest to the majority pattern (the gamma
+rate). With --min-match, it also lists the lines agreeing with the majority
+pattern in at least K columns.";
 
-    let gamma_rate_decimal = bin_to_dec(&gamma_rate);
-    let echo_rate_decimal = bin_to_dec(&echo_rate);
-
-    println!("Gamma rate decimal: {}", gamma_rate_decimal);
-    println!("Echo rate decimal: {}", echo_rate_decimal);
-    println!("Product: {}", gamma_rate_decimal * echo_rate_decimal);
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
 }
 
-fn part2(l: &[String]) {
-    let line_len = l[0].len();
-
-    let mut candidates = (0..l.len()).collect::<Vec<usize>>();
-
-    for pos in 0..line_len {
-        let mut counter = 0;
-        for &cand in candidates.iter() {
-            let bit = l[cand].chars().nth(pos).unwrap();
-            if bit == '1' {
-                counter += 1;
-            } else {
-                counter -= 1;
+fn query_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut source = InputSource::bundled(3);
+    let mut min_match = None;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                source = InputSource::from_arg(path);
             }
-        }
-        let most_common = if counter >= 0 { '1' } else { '0' };
-
-        candidates = candidates
-            .into_iter()
-            .filter(|&x| l[x].chars().nth(pos).unwrap() == most_common)
-            .collect();
-
-        if candidates.len() == 1 {
-            break;
-        }
-    }
-
-    assert_eq!(candidates.len(), 1);
-    let oxygen_rate = &l[candidates[0]];
-
-    candidates = (0..l.len()).collect::<Vec<usize>>();
-
-    for pos in 0..line_len {
-        let mut counter = 0;
-        for &cand in candidates.iter() {
-            let bit = l[cand].chars().nth(pos).unwrap();
-            if bit == '1' {
-                counter += 1;
-            } else {
-                counter -= 1;
+            "--min-match" => {
+                let k = Params::parse_value("min-match", it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+                min_match = Some(k);
             }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
         }
-        let least_common = if counter >= 0 { '0' } else { '1' };
-
-        candidates = candidates
-            .into_iter()
-            .filter(|&x| l[x].chars().nth(pos).unwrap() == least_common)
-            .collect();
+    }
 
-        if candidates.len() == 1 {
-            break;
+    let input = source.read().unwrap_or_else(|e| {
+        eprintln!("error: cannot read {}: {}", source.name(), e);
+        exit(1);
+    });
+    let report = p3::parse_input(&input).unwrap_or_else(|e| {
+        eprint!("{}", e.render(&source.name(), &input));
+        exit(1);
+    });
+
+    let query = Query::new(&report);
+    println!("column   ones  zeros  entropy");
+    for (column, stats) in query.columns().iter().enumerate() {
+        println!(
+            "{:>6}  {:>5}  {:>5}  {:>7.3}",
+            column + 1,
+            stats.ones,
+            stats.zeros,
+            stats.entropy()
+        );
+    }
+    match query.nearest_to_majority() {
+        Some((i, distance)) => println!(
+            "closest to the majority pattern: line {} ({}), {} bits away",
+            i + 1,
+            report.line_bits(i),
+            distance
+        ),
+        None => println!("closest to the majority pattern: none"),
+    }
+    if let Some(k) = min_match {
+        let lines = query.matching(k).collect::<Vec<usize>>();
+        println!(
+            "{} lines match the majority pattern in at least {} columns",
+            lines.len(),
+            k
+        );
+        for i in lines {
+            println!("line {}: {}", i + 1, report.line_bits(i));
         }
     }
-
-    assert_eq!(candidates.len(), 1);
-    let co2_rate = &l[candidates[0]];
-
-    let oxygen_rate_decimal = bin_to_dec(oxygen_rate);
-    let co2_rate_decimal = bin_to_dec(co2_rate);
-
-    println!("Oxygen rate: {}", oxygen_rate);
-    println!("CO2 rate: {}", co2_rate);
-    println!("Oxygen rate decimal: {}", oxygen_rate_decimal);
-    println!("CO2 rate decimal: {}", co2_rate_decimal);
-    println!("Product: {}", oxygen_rate_decimal * co2_rate_decimal);
 }
 
 fn main() {
-    let l = std::io::stdin()
-        .lock()
-        .lines()
-        .map(|x| x.unwrap())
-        .collect::<Vec<String>>();
-
-    part1(&l);
-    part2(&l);
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("query") => query_main(&args[1..]),
+        _ => aoc_common::main::<p3::Day3>(),
+    }
 }
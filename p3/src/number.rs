@@ -0,0 +1,123 @@
+use aoc_common::Answer;
+use std::fmt::{Display, Formatter};
+use std::ops::Mul;
+
+/// Unsigned integer of any size, as little-endian 64-bit limbs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Number {
+    // Never ends with a zero limb, so that zero has no limbs
+    limbs: Vec<u64>,
+}
+
+// Largest power of ten that fits in a limb, used to print numbers
+const DECIMAL_BASE: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_DIGITS: usize = 19;
+
+impl Number {
+    pub fn from_limbs(limbs: &[u64]) -> Self {
+        let len = limbs.iter().rposition(|&l| l != 0).map_or(0, |i| i + 1);
+        Number {
+            limbs: limbs[..len].to_vec(),
+        }
+    }
+
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    // Divides in place by `divisor`, returning the remainder
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = remainder << 64 | *limb as u128;
+            *limb = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        remainder as u64
+    }
+}
+
+impl From<u128> for Number {
+    fn from(n: u128) -> Self {
+        Number::from_limbs(&[n as u64, (n >> 64) as u64])
+    }
+}
+
+impl Mul for &Number {
+    type Output = Number;
+
+    fn mul(self, other: &Number) -> Number {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let current = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        Number::from_limbs(&limbs)
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(n) = self.to_u128() {
+            return write!(f, "{}", n);
+        }
+        // Groups of 19 digits, the least significant first
+        let mut n = self.clone();
+        let mut groups = Vec::new();
+        while !n.limbs.is_empty() {
+            groups.push(n.div_rem_small(DECIMAL_BASE));
+        }
+        let mut groups = groups.iter().rev();
+        write!(f, "{}", groups.next().unwrap())?;
+        for group in groups {
+            write!(f, "{:0width$}", group, width = DECIMAL_DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Number> for Answer {
+    fn from(n: Number) -> Self {
+        match n.to_u128() {
+            Some(n) => Answer::from(n),
+            None => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplies_past_128_bits() {
+        let max = Number::from(u128::MAX);
+        assert_eq!(max.to_string(), u128::MAX.to_string());
+        let square = &max * &max;
+        assert_eq!(square.limbs(), [1, 0, u64::MAX - 1, u64::MAX]);
+        assert_eq!(square.to_u128(), None);
+        assert_eq!(
+            square.to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+        );
+        assert_eq!(&square * &Number::default(), Number::default());
+        assert_eq!(Number::default().to_string(), "0");
+    }
+}
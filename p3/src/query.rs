@@ -0,0 +1,135 @@
+use crate::report::Report;
+
+/// How the lines of a report are split in one column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnStats {
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+impl ColumnStats {
+    /// Bit of the column in the gamma rate: 1 only with strictly more ones
+    pub fn majority(&self) -> bool {
+        self.ones > self.zeros
+    }
+
+    /// Shannon entropy of the column in bits, from 0 when every line agrees
+    /// to 1 when they are evenly split.
+    pub fn entropy(&self) -> f64 {
+        let total = (self.ones + self.zeros) as f64;
+        [self.ones, self.zeros]
+            .iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| {
+                let p = n as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+/// Questions about a report beyond its rates and ratings.
+pub struct Query<'a> {
+    report: &'a Report,
+    columns: Vec<ColumnStats>,
+    // Words of the majority pattern, that is the gamma rate
+    majority: Vec<u64>,
+}
+
+impl<'a> Query<'a> {
+    pub fn new(report: &'a Report) -> Self {
+        let width = report.width();
+        let columns = report
+            .column_counts()
+            .into_iter()
+            .map(|ones| ColumnStats {
+                ones,
+                zeros: report.len() - ones,
+            })
+            .collect::<Vec<ColumnStats>>();
+
+        let mut majority = vec![0u64; width.div_ceil(64)];
+        for (column, stats) in columns.iter().enumerate() {
+            if stats.majority() {
+                let index = width - 1 - column;
+                majority[index / 64] |= 1 << (index % 64);
+            }
+        }
+
+        Query {
+            report,
+            columns,
+            majority,
+        }
+    }
+
+    /// Statistics of every column, from the left.
+    pub fn columns(&self) -> &[ColumnStats] {
+        &self.columns
+    }
+
+    /// Entropy of every column, from the left.
+    pub fn entropies(&self) -> Vec<f64> {
+        self.columns.iter().map(ColumnStats::entropy).collect()
+    }
+
+    /// Words of the majority pattern, like those of a line.
+    pub fn majority(&self) -> &[u64] {
+        &self.majority
+    }
+
+    /// Lines agreeing with the majority pattern in at least `k` columns.
+    pub fn matching(&self, k: usize) -> impl Iterator<Item = usize> + '_ {
+        let width = self.report.width();
+        (0..self.report.len())
+            .filter(move |&i| width - self.report.distance(i, &self.majority) as usize >= k)
+    }
+
+    /// Line closest to `pattern` in Hamming distance, the first one on ties,
+    /// with its distance. `None` for an empty report.
+    pub fn nearest(&self, pattern: &[u64]) -> Option<(usize, u32)> {
+        (0..self.report.len())
+            .map(|i| (i, self.report.distance(i, pattern)))
+            .min_by_key(|&(i, distance)| (distance, i))
+    }
+
+    /// Line closest to the gamma rate, with its distance.
+    pub fn nearest_to_majority(&self) -> Option<(usize, u32)> {
+        self.nearest(&self.majority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, rates};
+
+    const SAMPLE: &str =
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
+    #[test]
+    fn answers_questions_about_the_sample() {
+        let report = parse_input(SAMPLE).unwrap();
+        let query = Query::new(&report);
+        assert_eq!(query.columns()[0], ColumnStats { ones: 7, zeros: 5 });
+        assert_eq!(query.majority(), rates(&report).0.limbs());
+
+        let entropies = query.entropies();
+        assert!((entropies[0] - 0.97987).abs() < 1e-5);
+        assert!(entropies.iter().all(|&e| e > 0.0 && e <= 1.0));
+
+        // The gamma rate 10110 is itself a line
+        assert_eq!(query.nearest_to_majority(), Some((2, 0)));
+        assert_eq!(query.matching(5).collect::<Vec<usize>>(), [2]);
+        assert_eq!(query.matching(4).collect::<Vec<usize>>(), [1, 2, 3]);
+        assert_eq!(query.matching(0).count(), 12);
+    }
+
+    #[test]
+    fn splits_evenly_at_one_bit_of_entropy() {
+        let report = parse_input("01\n11\n").unwrap();
+        let query = Query::new(&report);
+        assert_eq!(query.entropies(), [1.0, 0.0]);
+        assert_eq!(query.nearest(&[0]), Some((0, 1)));
+    }
+}
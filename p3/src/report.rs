@@ -0,0 +1,150 @@
+use aoc_common::{input_lines, ParseError};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Diagnostic report lines of any width, each packed into 64-bit words.
+///
+/// The words of a line are the little-endian limbs of its value, so the
+/// leftmost column of a line is its most significant bit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    width: usize,
+    // Words per line
+    stride: usize,
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl Report {
+    pub fn new(width: usize) -> Self {
+        Report {
+            width,
+            stride: width.div_ceil(WORD_BITS),
+            len: 0,
+            words: Vec::new(),
+        }
+    }
+
+    /// Appends a line of `width` bits, the first one being the leftmost.
+    pub fn push(&mut self, bits: impl IntoIterator<Item = bool>) {
+        let start = self.words.len();
+        self.words.resize(start + self.stride, 0);
+        let mut width = 0;
+        for (column, bit) in bits.into_iter().enumerate() {
+            if bit {
+                let index = self.width - 1 - column;
+                self.words[start + index / WORD_BITS] |= 1 << (index % WORD_BITS);
+            }
+            width += 1;
+        }
+        assert_eq!(width, self.width, "line of the wrong width");
+        self.len += 1;
+    }
+
+    /// Number of bits of every line
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of lines
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Words of line `i`, the least significant first.
+    pub fn line(&self, i: usize) -> &[u64] {
+        &self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    /// Bit of line `i` in `column`, counting from the left.
+    pub fn bit(&self, i: usize, column: usize) -> bool {
+        let index = self.width - 1 - column;
+        self.line(i)[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    /// Line `i` as it was written.
+    pub fn line_bits(&self, i: usize) -> String {
+        (0..self.width)
+            .map(|column| if self.bit(i, column) { '1' } else { '0' })
+            .collect()
+    }
+
+    /// Number of columns in which line `i` differs from `pattern`, given as
+    /// words like those of a line.
+    pub fn distance(&self, i: usize, pattern: &[u64]) -> u32 {
+        self.line(i)
+            .iter()
+            .zip(pattern)
+            .map(|(word, other)| (word ^ other).count_ones())
+            .sum()
+    }
+
+    /// Number of lines with a 1 in each column, from the left, counted in a
+    /// single pass over the set bits.
+    pub fn column_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.width];
+        for (i, &word) in self.words.iter().enumerate() {
+            let base = (i % self.stride) * WORD_BITS;
+            let mut rest = word;
+            while rest != 0 {
+                let index = base + rest.trailing_zeros() as usize;
+                counts[self.width - 1 - index] += 1;
+                rest &= rest - 1;
+            }
+        }
+        counts
+    }
+}
+
+pub fn parse_report(input: &str) -> Result<Report, ParseError> {
+    let mut report: Option<Report> = None;
+
+    for line in input_lines(input) {
+        let bits = line.check_chars(|c| c == '0' || c == '1', "a binary digit")?;
+        // Every report line must be as wide as the first one
+        let report = report.get_or_insert_with(|| Report::new(bits.len()));
+        let expected_len = report.width();
+        if bits.len() < expected_len {
+            return Err(line.end_error(format!("{} bits", expected_len)));
+        }
+        if bits.len() > expected_len {
+            return Err(line.error(&bits[expected_len..], "end of line"));
+        }
+        report.push(bits.bytes().map(|b| b == b'1'));
+    }
+    report.ok_or_else(|| ParseError::end_of_input(input, "a report line"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_lines_wider_than_a_word() {
+        let wide = format!("1{}11\n{}\n", "0".repeat(127), "01".repeat(65));
+        let report = parse_report(&wide).unwrap();
+        assert_eq!((report.width(), report.len()), (130, 2));
+        assert_eq!(report.line(0), [3, 0, 2]);
+        assert!(report.bit(0, 0) && !report.bit(0, 1) && report.bit(0, 129));
+        let counts = report.column_counts();
+        assert_eq!(counts[..4], [1, 1, 0, 1]);
+        assert_eq!(counts[128..], [1, 2]);
+        assert_eq!(counts.iter().sum::<usize>(), 3 + 65);
+    }
+
+    #[test]
+    fn rejects_ragged_lines() {
+        let e = parse_report("0101\n011\n").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (2, "4 bits"));
+    }
+
+    #[test]
+    fn rejects_empty_reports() {
+        let e = parse_report("").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (1, "a report line"));
+    }
+}
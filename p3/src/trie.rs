@@ -0,0 +1,162 @@
+use crate::report::Report;
+
+/// Which bit to follow at each column when filtering report lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+/// Bit criteria of the ratings: which bit to keep at each column, and which
+/// one to prefer when there are as many ones as zeros.
+///
+/// A column where every remaining line has the same bit keeps them all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Criteria {
+    pub keep: Keep,
+    pub on_tie: bool,
+}
+
+pub const OXYGEN: Criteria = Criteria {
+    keep: Keep::MostCommon,
+    on_tie: true,
+};
+
+pub const CO2: Criteria = Criteria {
+    keep: Keep::LeastCommon,
+    on_tie: false,
+};
+
+impl Criteria {
+    fn choose(&self, zeros: usize, ones: usize) -> bool {
+        if zeros == 0 || ones == 0 {
+            ones > 0
+        } else if zeros == ones {
+            self.on_tie
+        } else {
+            (ones > zeros) == (self.keep == Keep::MostCommon)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    // Index of the child following each bit; 0 for none, as the root is
+    // nobody's child
+    children: [usize; 2],
+    // Number of lines below
+    count: usize,
+    // First of those lines
+    line: usize,
+}
+
+/// Binary trie over the lines of a report, the leftmost column at the root.
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    pub fn new(report: &Report) -> Self {
+        let empty = Node {
+            children: [0; 2],
+            count: 0,
+            line: 0,
+        };
+        let mut nodes = vec![empty];
+        for i in 0..report.len() {
+            let mut node = 0;
+            for column in 0..report.width() {
+                if nodes[node].count == 0 {
+                    nodes[node].line = i;
+                }
+                nodes[node].count += 1;
+                let bit = report.bit(i, column) as usize;
+                if nodes[node].children[bit] == 0 {
+                    nodes[node].children[bit] = nodes.len();
+                    nodes.push(empty);
+                }
+                node = nodes[node].children[bit];
+            }
+            if nodes[node].count == 0 {
+                nodes[node].line = i;
+            }
+            nodes[node].count += 1;
+        }
+        Trie { nodes }
+    }
+
+    fn count(&self, node: usize) -> usize {
+        if node == 0 {
+            0
+        } else {
+            self.nodes[node].count
+        }
+    }
+
+    /// Line left once `criteria` has filtered the report one column at a
+    /// time, or `None` for an empty report. Of several equal lines, the first
+    /// one is returned.
+    pub fn find(&self, criteria: Criteria) -> Option<usize> {
+        let mut node = &self.nodes[0];
+        if node.count == 0 {
+            return None;
+        }
+        // Only one line is left once a single one goes through the node
+        while node.count > 1 && node.children != [0; 2] {
+            let [zeros, ones] = node.children.map(|child| self.count(child));
+            let bit = criteria.choose(zeros, ones);
+            node = &self.nodes[node.children[bit as usize]];
+        }
+        Some(node.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    const SAMPLE: &str =
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
+    // Filters candidates one column at a time, as the puzzle describes it
+    fn filter(report: &Report, criteria: Criteria) -> usize {
+        let mut candidates = (0..report.len()).collect::<Vec<usize>>();
+        for column in 0..report.width() {
+            if candidates.len() == 1 {
+                break;
+            }
+            let ones = candidates
+                .iter()
+                .filter(|&&x| report.bit(x, column))
+                .count();
+            let bit = criteria.choose(candidates.len() - ones, ones);
+            candidates.retain(|&x| report.bit(x, column) == bit);
+        }
+        candidates[0]
+    }
+
+    #[test]
+    fn finds_the_sample_ratings() {
+        let report = parse_input(SAMPLE).unwrap();
+        let trie = Trie::new(&report);
+        assert_eq!(trie.find(OXYGEN), Some(3));
+        assert_eq!(trie.find(CO2), Some(11));
+
+        for keep in [Keep::MostCommon, Keep::LeastCommon] {
+            for on_tie in [false, true] {
+                let criteria = Criteria { keep, on_tie };
+                assert_eq!(trie.find(criteria), Some(filter(&report, criteria)));
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_the_first_of_equal_lines() {
+        let report = parse_input("01\n11\n11\n").unwrap();
+        let trie = Trie::new(&report);
+        assert_eq!(trie.find(OXYGEN), Some(1));
+        assert_eq!(trie.find(CO2), Some(0));
+        assert_eq!(Trie::new(&Report::new(3)).find(OXYGEN), None);
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::{HashMap, VecDeque};
+
+/// A bingo card of any number of rows and columns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    pub rows: usize,
+    pub columns: usize,
+    /// Numbers row by row
+    pub cells: Vec<i32>,
+}
+
+impl Board {
+    pub fn new(rows: Vec<Vec<i32>>) -> Self {
+        let columns = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == columns), "ragged board");
+        Board {
+            rows: rows.len(),
+            columns,
+            cells: rows.concat(),
+        }
+    }
+}
+
+/// A way for a board to win: it does once every cell of one of its
+/// patterns is marked.
+pub trait WinCondition {
+    fn name(&self) -> &'static str;
+
+    /// Patterns of a board of that shape, as indices of cells row by row.
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>>;
+}
+
+pub struct Rows;
+
+impl WinCondition for Rows {
+    fn name(&self) -> &'static str {
+        "rows"
+    }
+
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>> {
+        (0..rows)
+            .map(|row| (0..columns).map(|col| row * columns + col).collect())
+            .collect()
+    }
+}
+
+pub struct Columns;
+
+impl WinCondition for Columns {
+    fn name(&self) -> &'static str {
+        "columns"
+    }
+
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>> {
+        (0..columns)
+            .map(|col| (0..rows).map(|row| row * columns + col).collect())
+            .collect()
+    }
+}
+
+/// Both diagonals, which only square boards have.
+pub struct Diagonals;
+
+impl WinCondition for Diagonals {
+    fn name(&self) -> &'static str {
+        "diagonals"
+    }
+
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>> {
+        if rows != columns || rows == 0 {
+            return Vec::new();
+        }
+        let size = rows;
+        vec![
+            (0..size).map(|i| i * size + i).collect(),
+            (0..size).map(|i| i * size + size - 1 - i).collect(),
+        ]
+    }
+}
+
+pub struct FourCorners;
+
+impl WinCondition for FourCorners {
+    fn name(&self) -> &'static str {
+        "corners"
+    }
+
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>> {
+        if rows == 0 || columns == 0 {
+            return Vec::new();
+        }
+        let last = rows * columns - 1;
+        let mut corners = vec![0, columns - 1, last + 1 - columns, last];
+        // A single row or column has only two corners
+        corners.sort_unstable();
+        corners.dedup();
+        vec![corners]
+    }
+}
+
+pub struct FullCard;
+
+impl WinCondition for FullCard {
+    fn name(&self) -> &'static str {
+        "full"
+    }
+
+    fn patterns(&self, rows: usize, columns: usize) -> Vec<Vec<usize>> {
+        vec![(0..rows * columns).collect()]
+    }
+}
+
+/// Win conditions of the puzzle.
+pub const PUZZLE_CONDITIONS: &[&dyn WinCondition] = &[&Rows, &Columns];
+
+/// Every win condition, to pick from by name.
+pub const CONDITIONS: &[&dyn WinCondition] =
+    &[&Rows, &Columns, &Diagonals, &FourCorners, &FullCard];
+
+pub fn condition(name: &str) -> Option<&'static dyn WinCondition> {
+    CONDITIONS.iter().copied().find(|c| c.name() == name)
+}
+
+/// A board completing one of its patterns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WinEvent {
+    pub board: usize,
+    /// Position in the draws of the number completing the pattern
+    pub draw_index: usize,
+    pub number: i32,
+    /// Sum of the numbers of the board left unmarked then
+    pub unmarked_sum: i32,
+}
+
+impl WinEvent {
+    pub fn score(&self) -> i32 {
+        self.number * self.unmarked_sum
+    }
+}
+
+/// Where a number is on a board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub board: usize,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Boards, the numbers drawn for them and the ways they may win.
+pub struct BingoGame {
+    draws: Vec<i32>,
+    boards: Vec<Board>,
+    // Cells holding each number, board by board
+    index: HashMap<i32, Vec<Cell>>,
+    // Winning patterns of each board, which depend on its shape
+    patterns: Vec<Vec<Vec<usize>>>,
+    // Patterns each cell of each board belongs to
+    memberships: Vec<Vec<Vec<usize>>>,
+}
+
+impl BingoGame {
+    pub fn new(draws: Vec<i32>, boards: Vec<Board>, conditions: &[&dyn WinCondition]) -> Self {
+        let mut index = HashMap::<i32, Vec<Cell>>::new();
+        let mut all_patterns = Vec::with_capacity(boards.len());
+        let mut memberships = Vec::with_capacity(boards.len());
+
+        for (id, board) in boards.iter().enumerate() {
+            for (cell, &number) in board.cells.iter().enumerate() {
+                index.entry(number).or_default().push(Cell {
+                    board: id,
+                    row: cell / board.columns,
+                    col: cell % board.columns,
+                });
+            }
+
+            let patterns = conditions
+                .iter()
+                .flat_map(|c| c.patterns(board.rows, board.columns))
+                .collect::<Vec<Vec<usize>>>();
+            let mut cells = vec![Vec::new(); board.cells.len()];
+            for (p, pattern) in patterns.iter().enumerate() {
+                for &cell in pattern {
+                    cells[cell].push(p);
+                }
+            }
+            all_patterns.push(patterns);
+            memberships.push(cells);
+        }
+
+        BingoGame {
+            draws,
+            boards,
+            index,
+            patterns: all_patterns,
+            memberships,
+        }
+    }
+
+    pub fn draws(&self) -> &[i32] {
+        &self.draws
+    }
+
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    /// Winning patterns of board `board`, as indices of its cells.
+    pub fn patterns(&self, board: usize) -> &[Vec<usize>] {
+        &self.patterns[board]
+    }
+
+    /// Cells holding `number`, board by board.
+    pub fn cells_of(&self, number: i32) -> &[Cell] {
+        self.index
+            .get(&number)
+            .map_or(&[], |cells| cells.as_slice())
+    }
+
+    /// Every board that wins, in the order they do, as the numbers are
+    /// drawn. Boards winning on the same draw come in the order of the
+    /// input.
+    pub fn events(&self) -> WinEvents<'_> {
+        self.events_for(&self.draws)
+    }
+
+    /// Same as `events`, drawing `draws` instead of the game's numbers.
+    pub fn events_for<'a>(&'a self, draws: &'a [i32]) -> WinEvents<'a> {
+        WinEvents {
+            game: self,
+            draws,
+            draw_index: 0,
+            hits: self
+                .patterns
+                .iter()
+                .map(|patterns| vec![0; patterns.len()])
+                .collect(),
+            marked: self
+                .boards
+                .iter()
+                .map(|board| vec![false; board.cells.len()])
+                .collect(),
+            unmarked_sums: self
+                .boards
+                .iter()
+                .map(|board| board.cells.iter().sum())
+                .collect(),
+            won: vec![false; self.boards.len()],
+            playing: self.boards.len(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn first_winner(&self) -> Option<WinEvent> {
+        self.events().next()
+    }
+
+    pub fn last_winner(&self) -> Option<WinEvent> {
+        self.events().last()
+    }
+}
+
+/// Iterator over the wins of a game, calling one number at a time.
+pub struct WinEvents<'a> {
+    game: &'a BingoGame,
+    draws: &'a [i32],
+    // Position of the next number to draw
+    draw_index: usize,
+    // Marked cells of each pattern of each board
+    hits: Vec<Vec<usize>>,
+    marked: Vec<Vec<bool>>,
+    unmarked_sums: Vec<i32>,
+    won: Vec<bool>,
+    // Boards yet to win
+    playing: usize,
+    // Wins of the last number drawn not returned yet
+    pending: VecDeque<WinEvent>,
+}
+
+impl WinEvents<'_> {
+    // Marks every cell holding the next number, queueing the wins
+    fn call(&mut self) {
+        let game = self.game;
+        let number = self.draws[self.draw_index];
+        let mut winners = Vec::new();
+
+        for cell in game.cells_of(number) {
+            let board = cell.board;
+            let i = cell.row * game.boards[board].columns + cell.col;
+            if self.won[board] || self.marked[board][i] {
+                continue;
+            }
+            self.marked[board][i] = true;
+            self.unmarked_sums[board] -= number;
+            for &p in &game.memberships[board][i] {
+                self.hits[board][p] += 1;
+                if self.hits[board][p] == game.patterns[board][p].len()
+                    && winners.last() != Some(&board)
+                {
+                    winners.push(board);
+                }
+            }
+        }
+
+        // Cells are listed board by board, so a board's sum is final by the
+        // time the draw is over
+        for board in winners {
+            self.won[board] = true;
+            self.playing -= 1;
+            self.pending.push_back(WinEvent {
+                board,
+                draw_index: self.draw_index,
+                number,
+                unmarked_sum: self.unmarked_sums[board],
+            });
+        }
+        self.draw_index += 1;
+    }
+}
+
+impl Iterator for WinEvents<'_> {
+    type Item = WinEvent;
+
+    fn next(&mut self) -> Option<WinEvent> {
+        while self.pending.is_empty() && self.playing > 0 && self.draw_index < self.draws.len() {
+            self.call();
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(rows: &[&[i32]]) -> Board {
+        Board::new(rows.iter().map(|row| row.to_vec()).collect())
+    }
+
+    #[test]
+    fn lays_out_patterns() {
+        assert_eq!(Rows.patterns(2, 3), [vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(Columns.patterns(2, 3), [vec![0, 3], vec![1, 4], vec![2, 5]]);
+        assert_eq!(Diagonals.patterns(3, 3), [vec![0, 4, 8], vec![2, 4, 6]]);
+        assert!(Diagonals.patterns(2, 3).is_empty());
+        assert_eq!(FourCorners.patterns(2, 3), [vec![0, 2, 3, 5]]);
+        assert_eq!(FourCorners.patterns(1, 3), [vec![0, 2]]);
+        assert_eq!(FullCard.patterns(1, 2), [vec![0, 1]]);
+    }
+
+    #[test]
+    fn plays_with_any_conditions() {
+        let boards = vec![
+            board(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]),
+            board(&[&[9, 8], &[7, 6], &[5, 4]]),
+        ];
+        let draws = vec![1, 5, 9, 3, 7, 2, 4, 6, 8];
+
+        let game = BingoGame::new(draws.clone(), boards.clone(), PUZZLE_CONDITIONS);
+        let winners = game.events().collect::<Vec<WinEvent>>();
+        assert_eq!(
+            winners[0],
+            WinEvent {
+                board: 1,
+                draw_index: 4,
+                number: 7,
+                unmarked_sum: 18
+            }
+        );
+        assert_eq!(
+            game.last_winner().map(|w| (w.board, w.number)),
+            Some((0, 2))
+        );
+
+        let game = BingoGame::new(draws.clone(), boards.clone(), &[&Diagonals]);
+        assert_eq!(game.first_winner().map(|w| w.score()), Some(9 * 30));
+        // The second board is not square, so it never wins
+        assert_eq!(game.events().count(), 1);
+
+        let game = BingoGame::new(draws, boards, &[&FourCorners, &FullCard]);
+        let order = game
+            .events()
+            .map(|w| (w.board, w.number))
+            .collect::<Vec<_>>();
+        assert_eq!(order, [(0, 7), (1, 8)]);
+    }
+
+    #[test]
+    fn counts_repeated_numbers_once() {
+        let boards = vec![board(&[&[1, 1], &[2, 3]]), board(&[&[3, 4], &[5, 6]])];
+        let game = BingoGame::new(vec![3, 3, 1, 6, 4], boards, PUZZLE_CONDITIONS);
+        assert_eq!(
+            game.cells_of(1),
+            [
+                Cell {
+                    board: 0,
+                    row: 0,
+                    col: 0
+                },
+                Cell {
+                    board: 0,
+                    row: 0,
+                    col: 1
+                }
+            ]
+        );
+        let events = game.events().collect::<Vec<WinEvent>>();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            (
+                events[0].board,
+                events[0].draw_index,
+                events[0].unmarked_sum
+            ),
+            (0, 2, 2)
+        );
+        assert_eq!(
+            (
+                events[1].board,
+                events[1].draw_index,
+                events[1].unmarked_sum
+            ),
+            (1, 4, 5)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use aoc_common::{input_lines, Param, Params, ParseError, Rng, Solution};
+
+pub mod game;
+pub mod strategy;
+
+use game::{BingoGame, Board, PUZZLE_CONDITIONS};
+
+const BOARD_SIZE: usize = 5;
+
+/// Reads the drawn numbers and the boards, each as many rows as there are
+/// lines until the next blank one and as many columns as its first row has
+/// numbers.
+pub fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    let mut input_iterator = input_lines(input).fuse().peekable();
+    let first_line = input_iterator
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "the drawn numbers"))?;
+    let rand_numbers = first_line
+        .text
+        .split(',')
+        .map(|x| first_line.parse::<i32>(x, "a number"))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+
+    let mut boards = Vec::<Board>::new();
+
+    while let Some(separator) = input_iterator.next() {
+        separator.blank()?;
+
+        let first_row = input_iterator
+            .next()
+            .ok_or_else(|| ParseError::end_of_input(input, "a board row"))?;
+        let row = first_row
+            .text
+            .split_whitespace()
+            .map(|x| first_row.parse::<i32>(x, "a number"))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+        if row.is_empty() {
+            return Err(first_row.end_error("a number"));
+        }
+        let columns = row.len();
+        let mut board = vec![row];
+
+        while let Some(line) = input_iterator.next_if(|line| !line.text.is_empty()) {
+            let mut tokenizer = line.text.split_whitespace();
+            let row = (0..columns)
+                .map(|_| line.parse_next::<i32>(&mut tokenizer, "a number"))
+                .collect::<Result<Vec<i32>, ParseError>>()?;
+            line.finish(&mut tokenizer)?;
+
+            board.push(row);
+        }
+
+        boards.push(Board::new(board));
+    }
+
+    Ok((rand_numbers, boards))
+}
+
+/// Score of the first board to win, if any does.
+pub fn part1(game: &BingoGame) -> Option<i32> {
+    game.first_winner().map(|w| w.score())
+}
+
+/// Score of the last board to win, if any does.
+pub fn part2(game: &BingoGame) -> Option<i32> {
+    game.last_winner().map(|w| w.score())
+}
+
+const NO_WINNER: &str = "no board wins";
+
+/// The drawn numbers followed by `size` boards of `board_size` x `board_size`
+/// distinct numbers. Every number is drawn, so every board wins eventually.
+pub fn generate(rng: &mut Rng, size: usize, board_size: usize) -> String {
+    let cells = board_size * board_size;
+    let mut numbers = (0..(4 * cells).max(100)).collect::<Vec<usize>>();
+    let width = (numbers.len() - 1).to_string().len();
+
+    rng.shuffle(&mut numbers);
+    let mut result = numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    result.push('\n');
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        result.push('\n');
+        for row in numbers[..cells].chunks(board_size) {
+            let row = row
+                .iter()
+                .map(|n| format!("{:>width$}", n, width = width))
+                .collect::<Vec<String>>()
+                .join(" ");
+            result.push_str(&row);
+            result.push('\n');
+        }
+    }
+    result
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input = BingoGame;
+    type Answer1 = Result<i32, &'static str>;
+    type Answer2 = Result<i32, &'static str>;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "board-size",
+        help: "number of rows and columns of generated boards (default: 5)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (draws, boards) = parse_input(input)?;
+        Ok(BingoGame::new(draws, boards, PUZZLE_CONDITIONS))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input).ok_or(NO_WINNER)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input).ok_or(NO_WINNER)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> String {
+        generate(rng, size, params.get("board-size").unwrap_or(BOARD_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Answer;
+
+    #[test]
+    fn answers_none_when_no_board_wins() {
+        for input in ["1\n\n1 2\n3 4\n", "7\n"] {
+            let game = Day4::parse(input).unwrap();
+            assert_eq!((part1(&game), part2(&game)), (None, None));
+            assert_eq!(
+                Answer::from(Day4::part1(&game)).to_string(),
+                "none (no board wins)"
+            );
+        }
+        let game = Day4::parse("3,1\n\n1 2\n3 4\n").unwrap();
+        assert_eq!((part1(&game), part2(&game)), (Some(6), Some(6)));
+    }
+}
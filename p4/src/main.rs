@@ -1,131 +1,228 @@
-type Board = Vec<Vec<i32>>;
-
-fn parse_input<T: std::io::BufRead>(input: T) -> (Vec<i32>, Vec<Board>) {
-    let mut input_iterator = input.lines().fuse().peekable();
-    let rand_numbers = input_iterator
-        .next()
-        .unwrap()
-        .unwrap()
-        .split(',')
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>();
-
-    input_iterator.next();
-
-    let mut boards = Vec::<Board>::new();
-
-    while input_iterator.peek().is_some() {
-        boards.push(Vec::<Vec<i32>>::with_capacity(5));
-        let idx = boards.len() - 1;
-
-        for _ in 0..5 {
-            let line = input_iterator
-                .next()
-                .unwrap()
-                .unwrap()
-                .split_whitespace()
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>();
-
-            boards[idx].push(line);
-        }
+use aoc_common::{default_seed, InputSource, Params, Rng};
+use p4::game::{self, BingoGame, WinCondition, WinEvent};
+use p4::strategy;
+use std::process::exit;
 
-        input_iterator.next();
-    }
+const USAGE: &str = "Usage: p4 play [--input <path>] [--win <condition>]... [--rank]
+       p4 prefix [--input <path>] [--win <condition>]... [--board K]
+       p4 shuffle [--input <path>] [--win <condition>]... [--rounds N] [--seed N]
+
+Boards win when any of the given conditions completes them (default: rows
+and columns, as in the puzzle). Conditions are rows, columns, diagonals,
+corners and full.
+
+`play` prints the first and last boards to win. With --rank, every board
+that wins is listed in the order it does.
+
+`prefix` prints, for board K or else every board, the shortest order in
+which to draw some of the numbers so that the board wins first.
+
+`shuffle` draws the numbers in N random orders (default: 10000) and counts
+how often each board wins first and last.";
 
-    (rand_numbers, boards)
+const DEFAULT_ROUNDS: usize = 10000;
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
 }
 
-fn part1(rand_numbers: &[i32], boards: &[Board]) {
-    let mut marks = vec![[[false; 5]; 5]; boards.len()];
-    let mut winning_board_opt = None;
-    let mut winning_number_opt = None;
+/// Options every subcommand takes to set up the game.
+struct GameOptions {
+    source: InputSource,
+    conditions: Vec<&'static dyn WinCondition>,
+}
 
-    for &rand_number in rand_numbers {
-        for (board_id, board) in boards.iter().enumerate() {
-            for (row, board) in board.iter().enumerate() {
-                board
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, &x)| x == rand_number)
-                    .for_each(|(col, _)| marks[board_id][row][col] = true);
-            }
+impl GameOptions {
+    fn new() -> Self {
+        GameOptions {
+            source: InputSource::bundled(4),
+            conditions: Vec::new(),
+        }
+    }
 
-            let any_row_complete = (0..5).any(|row| (0..5).all(|col| marks[board_id][row][col]));
-            let any_col_complete = (0..5).any(|col| (0..5).all(|row| marks[board_id][row][col]));
-            if any_row_complete || any_col_complete {
-                winning_board_opt = Some(board_id);
-                break;
+    // Reads `arg` and its value if it is one of these options
+    fn read<'a>(&mut self, arg: &str, it: &mut impl Iterator<Item = &'a String>) -> bool {
+        match arg {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                self.source = InputSource::from_arg(path);
             }
+            "--win" => {
+                let name = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--win expects a condition"));
+                let condition = game::condition(name)
+                    .unwrap_or_else(|| usage_error(&format!("unknown win condition '{}'", name)));
+                self.conditions.push(condition);
+            }
+            _ => return false,
         }
-        if winning_board_opt.is_some() {
-            winning_number_opt = Some(rand_number);
-            break;
-        }
+        true
     }
 
-    let winning_board = winning_board_opt.unwrap();
-    let winning_number = winning_number_opt.unwrap();
-
-    let result = winning_number
-        * (0usize..5).fold(0, |acc, row| {
-            acc + (0usize..5)
-                .filter(|&col| !marks[winning_board][row][col])
-                .fold(0, |acc_row, col| acc_row + boards[winning_board][row][col])
+    fn game(self) -> BingoGame {
+        let source = self.source;
+        let input = source.read().unwrap_or_else(|e| {
+            eprintln!("error: cannot read {}: {}", source.name(), e);
+            exit(1);
+        });
+        let (draws, boards) = p4::parse_input(&input).unwrap_or_else(|e| {
+            eprint!("{}", e.render(&source.name(), &input));
+            exit(1);
         });
+        if self.conditions.is_empty() {
+            BingoGame::new(draws, boards, game::PUZZLE_CONDITIONS)
+        } else {
+            BingoGame::new(draws, boards, &self.conditions)
+        }
+    }
+}
 
-    println!("{}", result);
+fn describe(name: &str, winner: Option<WinEvent>) {
+    match winner {
+        Some(w) => println!(
+            "{} winner: board {} on {}, {} unmarked, score {}",
+            name,
+            w.board + 1,
+            w.number,
+            w.unmarked_sum,
+            w.score()
+        ),
+        None => println!("{} winner: none", name),
+    }
 }
 
-fn part2(rand_numbers: &[i32], boards: &[Board]) {
-    let mut marks = vec![[[false; 5]; 5]; boards.len()];
-    let mut boards_won = vec![false; boards.len()];
-    let mut last_winning_board_opt = None;
-    let mut last_winning_number_opt = None;
+fn play_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut options = GameOptions::new();
+    let mut rank = false;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            option if options.read(option, &mut it) => {}
+            "--rank" => rank = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let game = options.game();
+    if rank {
+        println!("rank,board,draw,number,unmarked_sum,score");
+        for (i, w) in game.events().enumerate() {
+            println!(
+                "{},{},{},{},{},{}",
+                i + 1,
+                w.board + 1,
+                w.draw_index + 1,
+                w.number,
+                w.unmarked_sum,
+                w.score()
+            );
+        }
+    } else {
+        // Both come from the same pass over the draws
+        let mut events = game.events();
+        let first = events.next();
+        describe("first", first);
+        describe("last", events.last().or(first));
+    }
+}
 
-    for &rand_number in rand_numbers {
-        for (board_id, board) in boards.iter().enumerate() {
-            if boards_won[board_id] {
-                continue;
+fn prefix_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut options = GameOptions::new();
+    let mut board = None;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            option if options.read(option, &mut it) => {}
+            "--board" => {
+                let k = Params::parse_value("board", it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+                board = Some(k);
             }
-            for (row, board) in board.iter().enumerate() {
-                board
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, &x)| x == rand_number)
-                    .for_each(|(col, _)| marks[board_id][row][col] = true);
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
             }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
 
-            let any_row_complete = (0..5).any(|row| (0..5).all(|col| marks[board_id][row][col]));
-            let any_col_complete = (0..5).any(|col| (0..5).all(|row| marks[board_id][row][col]));
-            if any_row_complete || any_col_complete {
-                boards_won[board_id] = true;
-                last_winning_board_opt = Some(board_id);
-            }
+    let game = options.game();
+    let boards = match board {
+        Some(k) if k > game.boards().len() => {
+            usage_error(&format!("there are only {} boards", game.boards().len()))
         }
-        if boards_won.iter().all(|&x| x) {
-            last_winning_number_opt = Some(rand_number);
-            break;
+        Some(k) => k..=k,
+        None => 1..=game.boards().len(),
+    };
+    for k in boards {
+        match strategy::winning_prefix(&game, k - 1) {
+            Some(prefix) => {
+                let prefix = prefix
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<String>>();
+                println!("board {}: {}", k, prefix.join(","));
+            }
+            None => println!("board {}: never wins first", k),
         }
     }
+}
 
-    let last_winning_board = last_winning_board_opt.unwrap();
-    let last_winning_number = last_winning_number_opt.unwrap();
-
-    let result = last_winning_number
-        * (0usize..5).fold(0, |acc, row| {
-            acc + (0usize..5)
-                .filter(|&col| !marks[last_winning_board][row][col])
-                .fold(0, |acc_row, col| {
-                    acc_row + boards[last_winning_board][row][col]
-                })
-        });
+fn shuffle_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut options = GameOptions::new();
+    let mut rounds = DEFAULT_ROUNDS;
+    let mut seed = None;
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            option if options.read(option, &mut it) => {}
+            "--rounds" => {
+                rounds = Params::parse_value("rounds", it.next().map(|v| v.as_str()))
+                    .unwrap_or_else(|e| usage_error(&e));
+            }
+            "--seed" => {
+                let value = it.next().and_then(|v| v.parse::<u64>().ok());
+                seed = Some(value.unwrap_or_else(|| usage_error("--seed expects a number")));
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
 
-    println!("{}", result);
+    let seed = seed.unwrap_or_else(|| {
+        let seed = default_seed();
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let game = options.game();
+    let tally = strategy::shuffle_wins(&game, rounds, &mut Rng::new(seed));
+    println!("board,first,last");
+    for (k, (first, last)) in tally.first.iter().zip(&tally.last).enumerate() {
+        println!("{},{},{}", k + 1, first, last);
+    }
 }
 
 fn main() {
-    let (rand_numbers, boards) = parse_input(std::io::stdin().lock());
-    part1(&rand_numbers, &boards);
-    part2(&rand_numbers, &boards);
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("play") => play_main(&args[1..]),
+        Some("prefix") => prefix_main(&args[1..]),
+        Some("shuffle") => shuffle_main(&args[1..]),
+        _ => aoc_common::main::<p4::Day4>(),
+    }
 }
@@ -0,0 +1,171 @@
+use crate::game::BingoGame;
+use aoc_common::Rng;
+use std::collections::HashSet;
+
+// Distinct numbers of a pattern of `board`
+fn pattern_numbers(game: &BingoGame, board: usize, pattern: &[usize]) -> HashSet<i32> {
+    let cells = &game.boards()[board].cells;
+    pattern.iter().map(|&cell| cells[cell]).collect()
+}
+
+/// Shortest prefix of a reordering of the draws that makes board `board`
+/// win first, or `None` if no order does.
+///
+/// Drawing the numbers of one of its patterns is the quickest way for a
+/// board to win. It does so first unless another board completes a pattern
+/// of its own with those numbers: that one must then complete on the very
+/// last number drawn and come later in the input, so as to lose the tie.
+pub fn winning_prefix(game: &BingoGame, board: usize) -> Option<Vec<i32>> {
+    let drawn = game.draws().iter().copied().collect::<HashSet<i32>>();
+    let mut best: Option<Vec<i32>> = None;
+
+    for pattern in game.patterns(board) {
+        let numbers = pattern_numbers(game, board, pattern);
+        let shorter = best.as_ref().is_none_or(|b| numbers.len() < b.len());
+        if !shorter || !numbers.is_subset(&drawn) {
+            continue;
+        }
+
+        // Numbers which, drawn last, let no other board win before this one
+        let mut last_candidates = numbers.clone();
+        for other in (0..game.boards().len()).filter(|&other| other != board) {
+            for pattern in game.patterns(other) {
+                let others = pattern_numbers(game, other, pattern);
+                if others.is_subset(&numbers) {
+                    if other < board {
+                        last_candidates.clear();
+                    } else {
+                        last_candidates.retain(|n| others.contains(n));
+                    }
+                }
+            }
+        }
+
+        // Keep to the order of the draws as much as possible
+        let mut prefix = Vec::with_capacity(numbers.len());
+        let mut seen = HashSet::new();
+        for &n in game.draws() {
+            if numbers.contains(&n) && seen.insert(n) {
+                prefix.push(n);
+            }
+        }
+        let Some(last) = prefix.iter().rposition(|n| last_candidates.contains(n)) else {
+            continue;
+        };
+        let last = prefix.remove(last);
+        prefix.push(last);
+        best = Some(prefix);
+    }
+    best
+}
+
+/// The draws reordered to start with `prefix`, the other numbers following
+/// in their original order.
+pub fn reorder(draws: &[i32], prefix: &[i32]) -> Vec<i32> {
+    let mut rest = draws.to_vec();
+    for n in prefix {
+        if let Some(i) = rest.iter().position(|x| x == n) {
+            rest.remove(i);
+        }
+    }
+    prefix.iter().copied().chain(rest).collect()
+}
+
+/// How often each board won first and last over shuffles of the draws.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tally {
+    pub rounds: usize,
+    pub first: Vec<usize>,
+    pub last: Vec<usize>,
+}
+
+/// Plays `rounds` games, each drawing the numbers in a random order, and
+/// counts the boards winning first and last.
+pub fn shuffle_wins(game: &BingoGame, rounds: usize, rng: &mut Rng) -> Tally {
+    let boards = game.boards().len();
+    let mut tally = Tally {
+        rounds,
+        first: vec![0; boards],
+        last: vec![0; boards],
+    };
+    let mut draws = game.draws().to_vec();
+
+    for _ in 0..rounds {
+        rng.shuffle(&mut draws);
+        let mut events = game.events_for(&draws);
+        if let Some(first) = events.next() {
+            tally.first[first.board] += 1;
+            tally.last[events.last().unwrap_or(first).board] += 1;
+        }
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Board, Rows, CONDITIONS, PUZZLE_CONDITIONS};
+    use crate::parse_input;
+
+    const SAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn finds_a_prefix_for_every_board() {
+        let (draws, boards) = parse_input(SAMPLE).unwrap();
+        for conditions in [PUZZLE_CONDITIONS, CONDITIONS] {
+            let game = BingoGame::new(draws.clone(), boards.clone(), conditions);
+            for board in 0..boards.len() {
+                let prefix = winning_prefix(&game, board).unwrap();
+                let expected = if conditions.len() == 2 { 5 } else { 4 };
+                assert_eq!(prefix.len(), expected);
+
+                let reordered = reorder(&draws, &prefix);
+                let replay = BingoGame::new(reordered, boards.clone(), conditions);
+                let first = replay.first_winner().unwrap();
+                assert_eq!((first.board, first.draw_index), (board, prefix.len() - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn loses_ties_to_earlier_boards() {
+        let boards = vec![
+            Board::new(vec![vec![1, 2]]),
+            Board::new(vec![vec![1, 2, 3]]),
+        ];
+        let game = BingoGame::new(vec![3, 2, 1], boards, &[&Rows]);
+        // Board 1 cannot complete its row without board 0 completing its own
+        assert_eq!(winning_prefix(&game, 1), None);
+        assert_eq!(winning_prefix(&game, 0), Some(vec![2, 1]));
+    }
+
+    #[test]
+    fn tallies_shuffles() {
+        let (draws, boards) = parse_input(SAMPLE).unwrap();
+        let game = BingoGame::new(draws, boards, PUZZLE_CONDITIONS);
+        let tally = shuffle_wins(&game, 200, &mut Rng::new(7));
+        assert_eq!(tally.first.iter().sum::<usize>(), 200);
+        assert_eq!(tally.last.iter().sum::<usize>(), 200);
+        assert!(tally.first.iter().all(|&wins| wins > 0));
+        assert_eq!(tally, shuffle_wins(&game, 200, &mut Rng::new(7)));
+    }
+}
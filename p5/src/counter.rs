@@ -0,0 +1,125 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// A point on the ocean floor.
+pub type Point = (i64, i64);
+
+// Largest area counted on a dense grid, 16 MiB of counters; wider ones are
+// counted in a hash map, which only holds the points lines go through
+const DENSE_LIMIT: u128 = 1 << 22;
+
+/// Number of lines going through each point.
+#[derive(Clone, Debug)]
+pub enum Counter {
+    /// Grid of counters covering a rectangle
+    Dense {
+        min: Point,
+        width: usize,
+        height: usize,
+        cells: Vec<u32>,
+    },
+    Sparse(HashMap<Point, u32>),
+}
+
+impl Counter {
+    /// A counter for points within `min` and `max`, dense unless the
+    /// rectangle they span is too large.
+    pub fn for_bounds(min: Point, max: Point) -> Self {
+        let span = |low: i64, high: i64| (high as i128 - low as i128 + 1).max(0) as u128;
+        let (width, height) = (span(min.0, max.0), span(min.1, max.1));
+        match width.checked_mul(height) {
+            Some(area) if area <= DENSE_LIMIT => Counter::Dense {
+                min,
+                width: width as usize,
+                height: height as usize,
+                cells: vec![0; area as usize],
+            },
+            _ => Counter::Sparse(HashMap::new()),
+        }
+    }
+
+    pub fn is_dense(&self) -> bool {
+        matches!(self, Counter::Dense { .. })
+    }
+
+    pub fn add(&mut self, (x, y): Point) {
+        match self {
+            Counter::Dense {
+                min,
+                width,
+                height,
+                cells,
+            } => {
+                let (col, row) = ((x - min.0) as usize, (y - min.1) as usize);
+                assert!(col < *width && row < *height, "point out of bounds");
+                cells[row * *width + col] += 1;
+            }
+            Counter::Sparse(counts) => *counts.entry((x, y)).or_insert(0) += 1,
+        }
+    }
+
+    /// Number of lines through each point any line goes through.
+    pub fn counts(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        Box::new(self.points().map(|(_, n)| n))
+    }
+
+    /// Points any line goes through, with the number of lines through them.
+    pub fn points(&self) -> Box<dyn Iterator<Item = (Point, u32)> + '_> {
+        match self {
+            Counter::Dense {
+                min, width, cells, ..
+            } => Box::new(
+                cells
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &n)| n > 0)
+                    .map(move |(i, &n)| {
+                        let (row, col) = (i / *width, i % *width);
+                        ((min.0 + col as i64, min.1 + row as i64), n)
+                    }),
+            ),
+            Counter::Sparse(counts) => Box::new(counts.iter().map(|(&point, &n)| (point, n))),
+        }
+    }
+
+    /// Number of points going through each number of lines, leaving out
+    /// those no line goes through.
+    pub fn histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+        for n in self.counts() {
+            *histogram.entry(n).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Number of points at least `threshold` lines go through.
+    pub fn overlaps(&self, threshold: u32) -> usize {
+        self.counts().filter(|&n| n >= threshold).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_a_sparse_map_for_wide_ranges() {
+        let points = [(-3, 5), (0, 0), (-3, 5), (2, -1), (0, 0), (0, 0)];
+        let mut dense = Counter::for_bounds((-3, -1), (2, 5));
+        let mut sparse = Counter::for_bounds((-3_000_000, -1), (2_000_000, 5_000_000));
+        assert!(dense.is_dense() && !sparse.is_dense());
+        for point in points {
+            dense.add(point);
+            sparse.add(point);
+        }
+        for counter in [dense, sparse] {
+            assert_eq!(counter.overlaps(2), 2);
+            assert_eq!(counter.overlaps(3), 1);
+            assert_eq!(counter.counts().sum::<u32>(), 6);
+            assert_eq!(
+                counter.histogram().into_iter().collect::<Vec<_>>(),
+                [(1, 1), (2, 1), (3, 1)]
+            );
+        }
+        assert!(!Counter::for_bounds((i64::MIN, 0), (i64::MAX, 0)).is_dense());
+    }
+}
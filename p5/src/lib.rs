@@ -0,0 +1,360 @@
+use aoc_common::{input_lines, Line, Param, Params, ParseError, Rng, Solution};
+use std::collections::BTreeMap;
+
+pub mod counter;
+pub mod sweep;
+
+use counter::{Counter, Point};
+use sweep::{Coverage, Segment};
+
+const MIN_OVERLAP: u32 = 2;
+
+// Largest number of points to go through one by one before counting with
+// the sweep line instead
+const RASTER_LIMIT: u64 = 1 << 22;
+
+fn parse_point(line: &Line, token: &str) -> Result<Vec<i32>, ParseError> {
+    let mut tokenizer = token.split(',');
+    let x = line.parse_next::<i32>(&mut tokenizer, "an x coordinate")?;
+    let y = line.parse_next::<i32>(&mut tokenizer, "a y coordinate")?;
+    if let Some(extra) = tokenizer.next() {
+        return Err(line.error(extra, "` -> ` or end of line"));
+    }
+    Ok(vec![x, y])
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Vec<i32>>>, ParseError> {
+    input_lines(input)
+        .map(|line| {
+            let mut tokenizer = line.text.split(" -> ");
+            let point1 = parse_point(&line, line.next(&mut tokenizer, "a point")?)?;
+            let point2 = parse_point(&line, line.next(&mut tokenizer, "` -> `")?)?;
+            line.finish(&mut tokenizer)?;
+            Ok(vec![point1, point2])
+        })
+        .collect::<Result<Vec<Vec<Vec<i32>>>, ParseError>>()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Lattice points of a line from one end to the other, any slope it has
+fn points(coord: &[Vec<i32>]) -> impl Iterator<Item = Point> {
+    let (x1, y1) = (coord[0][0] as i64, coord[0][1] as i64);
+    let (x2, y2) = (coord[1][0] as i64, coord[1][1] as i64);
+    // Consecutive lattice points are a gcd-th of the way apart
+    let len = gcd(x2 - x1, y2 - y1);
+    let step = if len == 0 {
+        (0, 0)
+    } else {
+        ((x2 - x1) / len, (y2 - y1) / len)
+    };
+    (0..=len).map(move |i| (x1 + i * step.0, y1 + i * step.1))
+}
+
+// The lines the sweep engine handles, as segments, and the others
+fn split<'a>(lines: &[&'a Vec<Vec<i32>>]) -> (Vec<Segment>, Vec<&'a Vec<Vec<i32>>>) {
+    let mut segments = Vec::new();
+    let mut others = Vec::new();
+    for &coord in lines {
+        let point = |p: &Vec<i32>| (p[0] as i64, p[1] as i64);
+        match Segment::new(point(&coord[0]), point(&coord[1])) {
+            Some(segment) => segments.push(segment),
+            None => others.push(coord),
+        }
+    }
+    (segments, others)
+}
+
+// Smallest and largest coordinates of the ends of `coords`
+fn bounds<'a>(coords: impl Iterator<Item = &'a Vec<Vec<i32>>>) -> (Point, Point) {
+    coords.flatten().fold(
+        ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN)),
+        |(min, max), point| {
+            let (x, y) = (point[0] as i64, point[1] as i64);
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        },
+    )
+}
+
+// Horizontal and vertical lines, and the others too if `allow_diagonal_lines`
+fn select(coords: &[Vec<Vec<i32>>], allow_diagonal_lines: bool) -> Vec<&Vec<Vec<i32>>> {
+    coords
+        .iter()
+        .filter(|coord| {
+            allow_diagonal_lines || coord[0][0] == coord[1][0] || coord[0][1] == coord[1][1]
+        })
+        .collect()
+}
+
+// Goes through every point of `lines`
+fn rasterize(lines: &[&Vec<Vec<i32>>]) -> Counter {
+    let (min, max) = bounds(lines.iter().copied());
+    let mut counter = Counter::for_bounds(min, max);
+    for coord in lines {
+        points(coord).for_each(|point| counter.add(point));
+    }
+    counter
+}
+
+/// How overlaps are counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// Rasterizes when the lines go through few enough points, and sweeps
+    /// otherwise
+    Auto,
+    /// Goes through every point of every line
+    Raster,
+    /// Works out where horizontal, vertical and diagonal lines overlap and
+    /// cross with a sweep line, rasterizing only lines of other slopes
+    Sweep,
+}
+
+const ENGINES: [Engine; 3] = [Engine::Auto, Engine::Raster, Engine::Sweep];
+
+/// Names of the engines, as given to the `engine` param and `p5 histogram`.
+pub const ENGINE_NAMES: &[&str] = &["auto", "raster", "sweep"];
+
+impl Engine {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let index = ENGINE_NAMES.iter().position(|&engine| engine == name)?;
+        Some(ENGINES[index])
+    }
+}
+
+/// Number of points going through each number of lines, leaving out those
+/// no line goes through, counted with `engine`.
+pub fn histogram(
+    coords: &[Vec<Vec<i32>>],
+    allow_diagonal_lines: bool,
+    engine: Engine,
+) -> BTreeMap<u32, usize> {
+    let lines = select(coords, allow_diagonal_lines);
+
+    let length = || {
+        lines
+            .iter()
+            .map(|coord| points(coord).size_hint().0 as u64)
+            .sum::<u64>()
+    };
+    let sweep = match engine {
+        Engine::Auto => length() > RASTER_LIMIT,
+        Engine::Raster => false,
+        Engine::Sweep => true,
+    };
+    if !sweep {
+        return rasterize(&lines).histogram();
+    }
+
+    let (segments, others) = split(&lines);
+    let mut coverage = Coverage::new(&segments);
+    coverage.merge(&rasterize(&others));
+    coverage.histogram().clone()
+}
+
+/// Counts the points at least `min_overlap` lines go through.
+pub fn solution(
+    coords: &[Vec<Vec<i32>>],
+    allow_diagonal_lines: bool,
+    min_overlap: u32,
+    engine: Engine,
+) -> usize {
+    histogram(coords, allow_diagonal_lines, engine)
+        .range(min_overlap..)
+        .map(|(_, n)| n)
+        .sum()
+}
+
+// Coordinates of generated lines stay below this, as in the puzzle
+const GENERATE_RANGE: i64 = 1000;
+
+/// `size` random lines of vents, about a third each horizontal, vertical and
+/// diagonal.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = GENERATE_RANGE - 1;
+    let mut result = String::new();
+    for _ in 0..size {
+        let (x1, y1) = (rng.range(0..=max), rng.range(0..=max));
+        let (x2, y2) = match rng.below(3) {
+            0 => (x1, rng.range(0..=max)),
+            1 => (rng.range(0..=max), y1),
+            _ => {
+                let step_x = if rng.chance(1, 2) { 1 } else { -1 };
+                let step_y = if rng.chance(1, 2) { 1 } else { -1 };
+                let room_x = if step_x > 0 { max - x1 } else { x1 };
+                let room_y = if step_y > 0 { max - y1 } else { y1 };
+                let len = rng.range(0..=room_x.min(room_y));
+                (x1 + step_x * len, y1 + step_y * len)
+            }
+        };
+        result.push_str(&format!("{},{} -> {},{}\n", x1, y1, x2, y2));
+    }
+    result
+}
+
+fn min_overlap(param: Option<usize>) -> u32 {
+    param.map_or(MIN_OVERLAP, |n| n.min(u32::MAX as usize) as u32)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    // Lines, the number of them a point must go through if given, and how
+    // to count them
+    type Input = (Vec<Vec<Vec<i32>>>, Option<usize>, Engine);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "min-overlap",
+            help: "lines a point must go through to be counted (default: 2)",
+            values: &[],
+        },
+        Param {
+            name: "engine",
+            help: "how overlaps are counted (default: auto, by number of points)",
+            values: ENGINE_NAMES,
+        },
+    ];
+
+    const GENERATE_SIZE: usize = 500;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((
+            parse_input(input)?,
+            params.get("min-overlap"),
+            params
+                .get("engine")
+                .map_or(Engine::Auto, |index| ENGINES[index]),
+        ))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        solution(&input.0, false, min_overlap(input.1), input.2)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        solution(&input.0, true, min_overlap(input.1), input.2)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "0,9 -> 5,9\n8,0 -> 0,8\n9,4 -> 3,4\n2,2 -> 2,1\n7,0 -> 7,4\n6,4 -> 2,0\n0,9 -> 2,9\n3,4 -> 1,4\n0,0 -> 8,8\n5,5 -> 8,2\n";
+
+    #[test]
+    fn handles_negative_and_huge_coordinates() {
+        let coords = parse_input(SAMPLE).unwrap();
+        assert_eq!(
+            (
+                solution(&coords, false, 2, Engine::Auto),
+                solution(&coords, true, 2, Engine::Auto)
+            ),
+            (5, 12)
+        );
+
+        // Moved partly below zero, with a lone vent so far away that only a
+        // sparse counter fits
+        let mut moved = coords
+            .iter()
+            .map(|coord| {
+                coord
+                    .iter()
+                    .map(|p| vec![p[0] - 2_000_000_000, p[1] - 4])
+                    .collect()
+            })
+            .collect::<Vec<Vec<Vec<i32>>>>();
+        moved.push(vec![vec![1_000_000_000, 1_000_000_000]; 2]);
+        let (min, max) = bounds(moved.iter());
+        assert!(!Counter::for_bounds(min, max).is_dense());
+        assert_eq!(
+            (
+                solution(&moved, false, 2, Engine::Auto),
+                solution(&moved, true, 2, Engine::Auto)
+            ),
+            (5, 12)
+        );
+    }
+
+    #[test]
+    fn walks_any_slope() {
+        let steep = points(&[vec![0, 0], vec![6, -3]]).collect::<Vec<Point>>();
+        assert_eq!(steep, [(0, 0), (2, -1), (4, -2), (6, -3)]);
+        assert_eq!(points(&[vec![1, 1], vec![1, 1]]).count(), 1);
+        assert_eq!(points(&[vec![0, 0], vec![3, 7]]).count(), 2);
+
+        let coords = parse_input("0,0 -> 6,3\n0,3 -> 6,0\n4,2 -> 4,0\n").unwrap();
+        assert_eq!(solution(&coords, true, 2, Engine::Auto), 2);
+        assert_eq!(solution(&coords, false, 2, Engine::Auto), 0);
+    }
+
+    #[test]
+    fn counts_above_any_threshold() {
+        let coords = parse_input(SAMPLE).unwrap();
+        let histogram = histogram(&coords, true, Engine::Auto);
+        assert_eq!(histogram.range(2..).map(|(_, n)| n).sum::<usize>(), 12);
+        assert_eq!(histogram[&3], 2);
+        assert_eq!(solution(&coords, true, 3, Engine::Auto), 2);
+        assert_eq!(solution(&coords, true, 4, Engine::Auto), 0);
+        assert_eq!(
+            solution(&coords, true, 1, Engine::Auto),
+            histogram.values().sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn sweeps_long_lines_of_any_slope() {
+        // Billions of points, a diagonal through their crossing and a steep
+        // line through the end of the horizontal one
+        let coords =
+            parse_input("0,0 -> 2000000000,0\n5,-5 -> 5,2000000000\n3,-2 -> 7,2\n0,0 -> 10,-5\n")
+                .unwrap();
+        let histogram = histogram(&coords, true, Engine::Auto);
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            [(1, 4_000_000_013), (2, 1), (3, 1)]
+        );
+        assert_eq!(solution(&coords, true, 3, Engine::Auto), 1);
+        assert_eq!(solution(&coords, false, 2, Engine::Auto), 1);
+    }
+
+    #[test]
+    fn engines_agree() {
+        let mut input = SAMPLE.to_string();
+        input.push_str("0,0 -> 6,3\n-4,9 -> 8,-3\n");
+        let coords = parse_input(&input).unwrap();
+        for allow_diagonal_lines in [false, true] {
+            let expected = histogram(&coords, allow_diagonal_lines, Engine::Raster);
+            for engine in [Engine::Auto, Engine::Sweep] {
+                assert_eq!(histogram(&coords, allow_diagonal_lines, engine), expected);
+            }
+        }
+
+        assert_eq!(Engine::from_name("raster"), Some(Engine::Raster));
+        assert_eq!(Engine::from_name("fast"), None);
+        let param = Day5::PARAMS.iter().find(|p| p.name == "engine").unwrap();
+        assert!(param.parse(Some("fast")).is_err());
+        assert!(param.parse(Some("2")).is_err());
+        let mut params = Params::default();
+        params.set("engine", param.parse(Some("sweep")).unwrap());
+        assert_eq!(Day5::parse_with(SAMPLE, &params).unwrap().2, Engine::Sweep);
+    }
+}
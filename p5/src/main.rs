@@ -1,76 +1,74 @@
-fn parse_input(handle: impl std::io::BufRead) -> Vec<Vec<Vec<i32>>> {
-    handle
-        .lines()
-        .map(|l| {
-            l.unwrap()
-                .split(" -> ")
-                .map(|x| {
-                    x.split(',')
-                        .map(|y| y.parse::<i32>().unwrap())
-                        .collect::<Vec<i32>>()
-                })
-                .collect::<Vec<Vec<i32>>>()
-        })
-        .collect::<Vec<Vec<Vec<i32>>>>()
-}
+use aoc_common::InputSource;
+use p5::{Engine, ENGINE_NAMES};
+use std::process::exit;
+
+const USAGE: &str = "Usage: p5 histogram [--input <path>] [--straight] [--engine auto|raster|sweep]
 
-fn solution(coords: &Vec<Vec<Vec<i32>>>, allow_diagonal_lines: bool) {
-    let grid_size = (
-        coords
-            .iter()
-            .map(|coord| std::cmp::max(coord[0][0], coord[1][0]))
-            .max()
-            .unwrap()
-            + 1,
-        coords
-            .iter()
-            .map(|coord| std::cmp::max(coord[0][1], coord[1][1]))
-            .max()
-            .unwrap()
-            + 1,
-    );
+`histogram` prints, as CSV, how many points each number of lines goes
+through. --straight leaves out lines that are neither horizontal nor
+vertical, as part 1 does. --engine picks how overlaps are counted: raster
+visits every point, sweep follows horizontal, vertical and diagonal lines
+with a sweep line, and auto (the default) picks by the number of points.";
 
-    let mut grid = vec![vec![0; grid_size.1 as usize]; grid_size.0 as usize];
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
+}
 
-    for coord in coords {
-        let point1 = &coord[0];
-        let point2 = &coord[1];
+fn histogram_main(args: &[String]) {
+    let mut it = args.iter();
+    let mut source = InputSource::bundled(5);
+    let mut straight = false;
+    let mut engine = Engine::Auto;
 
-        if point1[0] == point2[0] {
-            // Vertical
-            let x = point1[0];
-            for y in std::cmp::min(point1[1], point2[1])..=std::cmp::max(point1[1], point2[1]) {
-                grid[x as usize][y as usize] += 1;
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => {
+                let path = it
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path"));
+                source = InputSource::from_arg(path);
             }
-        } else if point1[1] == point2[1] {
-            // Horizontal
-            let y = point1[1];
-            for x in std::cmp::min(point1[0], point2[0])..=std::cmp::max(point1[0], point2[0]) {
-                grid[x as usize][y as usize] += 1;
+            "--straight" => straight = true,
+            "--engine" => {
+                engine = it
+                    .next()
+                    .and_then(|name| Engine::from_name(name))
+                    .unwrap_or_else(|| {
+                        usage_error(&format!(
+                            "--engine expects one of {}",
+                            ENGINE_NAMES.join(", ")
+                        ))
+                    });
             }
-        } else if allow_diagonal_lines {
-            // Diagonal
-            let step: (i32, i32) = (
-                if point1[0] < point2[0] { 1 } else { -1 },
-                if point1[1] < point2[1] { 1 } else { -1 },
-            );
-
-            let (mut x, mut y) = (point1[0], point1[1]);
-
-            while x != point2[0] + step.0 && y != point2[1] + step.1 {
-                grid[x as usize][y as usize] += 1;
-                x += step.0;
-                y += step.1;
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
             }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
         }
     }
 
-    let result = grid.into_iter().flatten().filter(|&x| x >= 2).count();
-    println!("{}", result);
+    let input = source.read().unwrap_or_else(|e| {
+        eprintln!("error: cannot read {}: {}", source.name(), e);
+        exit(1);
+    });
+    let coords = p5::parse_input(&input).unwrap_or_else(|e| {
+        eprint!("{}", e.render(&source.name(), &input));
+        exit(1);
+    });
+
+    println!("lines,points");
+    for (lines, points) in p5::histogram(&coords, !straight, engine) {
+        println!("{},{}", lines, points);
+    }
 }
 
 fn main() {
-    let coords = parse_input(std::io::stdin().lock());
-    solution(&coords, false);
-    solution(&coords, true);
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("histogram") => histogram_main(&args[1..]),
+        _ => aoc_common::main::<p5::Day5>(),
+    }
 }
@@ -0,0 +1,377 @@
+use crate::counter::{Counter, Point};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Direction of a line the sweep engine handles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Family {
+    Horizontal,
+    Vertical,
+    /// Going up and right, along which `x - y` is constant
+    Diagonal,
+    /// Going down and right, along which `x + y` is constant
+    AntiDiagonal,
+}
+
+const FAMILIES: [Family; 4] = [
+    Family::Horizontal,
+    Family::Vertical,
+    Family::Diagonal,
+    Family::AntiDiagonal,
+];
+
+impl Family {
+    // What is constant along lines of the family, and what tells their
+    // points apart: x for all but vertical lines, which use y
+    fn line_and_position(self, (x, y): Point) -> (i64, i64) {
+        match self {
+            Family::Horizontal => (y, x),
+            Family::Vertical => (x, y),
+            Family::Diagonal => (x - y, x),
+            Family::AntiDiagonal => (x + y, x),
+        }
+    }
+}
+
+/// A line of vents as a range of positions along a line of its family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub family: Family,
+    pub line: i64,
+    pub low: i64,
+    pub high: i64,
+}
+
+impl Segment {
+    /// The segment between two points, if they are on a horizontal, vertical
+    /// or 45° line. A single point counts as horizontal.
+    pub fn new(start: Point, end: Point) -> Option<Self> {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let family = if dy == 0 {
+            Family::Horizontal
+        } else if dx == 0 {
+            Family::Vertical
+        } else if dx == dy {
+            Family::Diagonal
+        } else if dx == -dy {
+            Family::AntiDiagonal
+        } else {
+            return None;
+        };
+        let (line, a) = family.line_and_position(start);
+        let (_, b) = family.line_and_position(end);
+        Some(Segment {
+            family,
+            line,
+            low: a.min(b),
+            high: a.max(b),
+        })
+    }
+}
+
+// Reports the crossings of `stationary` segments, each standing at a time
+// and spanning a range of keys, with `moving` ones, each standing at a key
+// for a range of times, as (time, key) pairs. Several moving segments at the
+// same key are reported once.
+fn sweep(
+    stationary: &[(i64, i64, i64)],
+    moving: &[(i64, i64, i64)],
+    mut report: impl FnMut(i64, i64),
+) {
+    // At the same time, segments come in before queries and leave after
+    const INSERT: u8 = 0;
+    const QUERY: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events = Vec::with_capacity(stationary.len() + 2 * moving.len());
+    for &(time, low, high) in stationary {
+        events.push((time, QUERY, low, high));
+    }
+    for &(key, start, end) in moving {
+        events.push((start, INSERT, key, key));
+        events.push((end, REMOVE, key, key));
+    }
+    events.sort_unstable();
+
+    let mut active = BTreeMap::<i64, usize>::new();
+    for (time, kind, low, high) in events {
+        match kind {
+            INSERT => *active.entry(low).or_insert(0) += 1,
+            QUERY => active
+                .range(low..=high)
+                .for_each(|(&key, _)| report(time, key)),
+            _ => {
+                let count = active.get_mut(&low).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    active.remove(&low);
+                }
+            }
+        }
+    }
+}
+
+// Lattice points where segments of different families cross
+fn crossings(segments: &[Segment]) -> HashSet<Point> {
+    let of = |family: Family| {
+        segments
+            .iter()
+            .filter(move |s| s.family == family)
+            .map(|s| (s.line, s.low, s.high))
+    };
+    let mut points = HashSet::new();
+
+    // Sweeping over y, horizontal segments meet the others
+    let horizontal = of(Family::Horizontal).collect::<Vec<_>>();
+    let as_keys = |shift: fn(i64, i64) -> i64| {
+        horizontal
+            .iter()
+            .map(|&(y, low, high)| (y, shift(low, y), shift(high, y)))
+            .collect::<Vec<_>>()
+    };
+    let vertical = of(Family::Vertical).collect::<Vec<_>>();
+    sweep(&horizontal, &vertical, |y, x| {
+        points.insert((x, y));
+    });
+    let diagonal = of(Family::Diagonal)
+        .map(|(d, low, high)| (d, low - d, high - d))
+        .collect::<Vec<_>>();
+    sweep(&as_keys(|x, y| x - y), &diagonal, |y, d| {
+        points.insert((y + d, y));
+    });
+    let anti_diagonal = of(Family::AntiDiagonal)
+        .map(|(s, low, high)| (s, s - high, s - low))
+        .collect::<Vec<_>>();
+    sweep(&as_keys(|x, y| x + y), &anti_diagonal, |y, s| {
+        points.insert((s - y, y));
+    });
+
+    // Sweeping over x, vertical segments meet diagonal ones
+    let vertical_as = |to_key: fn(i64, i64) -> i64| {
+        vertical
+            .iter()
+            .map(|&(x, low, high)| {
+                let (a, b) = (to_key(x, low), to_key(x, high));
+                (x, a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>()
+    };
+    let diagonal = of(Family::Diagonal).collect::<Vec<_>>();
+    sweep(&vertical_as(|x, y| x - y), &diagonal, |x, d| {
+        points.insert((x, x - d));
+    });
+    let anti_diagonal = of(Family::AntiDiagonal).collect::<Vec<_>>();
+    sweep(&vertical_as(|x, y| x + y), &anti_diagonal, |x, s| {
+        points.insert((x, s - x));
+    });
+
+    // Sweeping over x + y, anti-diagonal segments meet diagonal ones, on a
+    // lattice point only when x + y and x - y have the same parity
+    let anti_diagonal = of(Family::AntiDiagonal)
+        .map(|(s, low, high)| (s, 2 * low - s, 2 * high - s))
+        .collect::<Vec<_>>();
+    let diagonal = of(Family::Diagonal)
+        .map(|(d, low, high)| (d, 2 * low - d, 2 * high - d))
+        .collect::<Vec<_>>();
+    sweep(&anti_diagonal, &diagonal, |s, d| {
+        if (s - d) % 2 == 0 {
+            points.insert(((s + d) / 2, (s - d) / 2));
+        }
+    });
+
+    points
+}
+
+// Sorted ranges of positions along a line, with the number of segments
+// covering them
+type Covered = Vec<(i64, i64, u32)>;
+
+/// Number of segments going through each point, kept as the depth of
+/// coverage along each line rather than point by point.
+pub struct Coverage {
+    lines: HashMap<(Family, i64), Covered>,
+    histogram: BTreeMap<u32, usize>,
+}
+
+impl Coverage {
+    pub fn new(segments: &[Segment]) -> Self {
+        let mut ranges = HashMap::<(Family, i64), Vec<(i64, i64)>>::new();
+        for s in segments {
+            ranges
+                .entry((s.family, s.line))
+                .or_default()
+                .push((s.low, s.high));
+        }
+
+        let mut lines = HashMap::new();
+        let mut histogram = BTreeMap::new();
+        for (line, ranges) in ranges {
+            let mut events = ranges
+                .iter()
+                .flat_map(|&(low, high)| [(low, 1), (high + 1, -1)])
+                .collect::<Vec<(i64, i32)>>();
+            events.sort_unstable();
+
+            let mut covered = Vec::new();
+            let mut depth = 0;
+            let mut start = 0;
+            for (position, change) in events {
+                if depth > 0 && position > start {
+                    covered.push((start, position - 1, depth as u32));
+                    *histogram.entry(depth as u32).or_insert(0) += (position - start) as usize;
+                }
+                depth += change;
+                start = position;
+            }
+            lines.insert(line, covered);
+        }
+        let mut coverage = Coverage { lines, histogram };
+
+        // Lines of different families only meet where segments cross, and
+        // each line counted those points on its own
+        for point in crossings(segments) {
+            let mut total = 0;
+            for depth in FAMILIES.map(|family| coverage.depth(family, point)) {
+                if depth > 0 {
+                    coverage.remove(depth);
+                    total += depth;
+                }
+            }
+            *coverage.histogram.entry(total).or_insert(0) += 1;
+        }
+        coverage
+    }
+
+    // Number of segments of `family` through `point`
+    fn depth(&self, family: Family, point: Point) -> u32 {
+        let (line, position) = family.line_and_position(point);
+        self.lines.get(&(family, line)).map_or(0, |ranges| {
+            let i = ranges.partition_point(|&(_, high, _)| high < position);
+            match ranges.get(i) {
+                Some(&(low, _, depth)) if low <= position => depth,
+                _ => 0,
+            }
+        })
+    }
+
+    fn remove(&mut self, depth: u32) {
+        let count = self.histogram.get_mut(&depth).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.histogram.remove(&depth);
+        }
+    }
+
+    /// Number of segments going through `point`.
+    pub fn at(&self, point: Point) -> u32 {
+        FAMILIES
+            .map(|family| self.depth(family, point))
+            .iter()
+            .sum()
+    }
+
+    /// Adds lines counted point by point, such as those of other slopes.
+    pub fn merge(&mut self, counter: &Counter) {
+        for (point, count) in counter.points() {
+            let depth = self.at(point);
+            if depth > 0 {
+                self.remove(depth);
+            }
+            *self.histogram.entry(depth + count).or_insert(0) += 1;
+        }
+    }
+
+    /// Number of points going through each number of segments, leaving out
+    /// those no segment goes through.
+    pub fn histogram(&self) -> &BTreeMap<u32, usize> {
+        &self.histogram
+    }
+
+    /// Number of points at least `threshold` segments go through.
+    pub fn overlaps(&self, threshold: u32) -> usize {
+        self.histogram
+            .range(threshold.max(1)..)
+            .map(|(_, n)| n)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::counter::Counter;
+    use aoc_common::Rng;
+
+    fn rasterized(lines: &[(Point, Point)]) -> Counter {
+        let mut counter = Counter::for_bounds((-50, -50), (50, 50));
+        for &(start, end) in lines {
+            let step = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+            let len = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+            for i in 0..=len {
+                counter.add((start.0 + i * step.0, start.1 + i * step.1));
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn matches_rasterizing() {
+        let mut rng = Rng::new(5);
+        for _ in 0..200 {
+            let lines = (0..rng.range(1..=30))
+                .map(|_| {
+                    let start = (rng.range(-10..=10), rng.range(-10..=10));
+                    let len = rng.range(0..=12);
+                    let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1), (-1, -1)]);
+                    (start, (start.0 + dx * len, start.1 + dy * len))
+                })
+                .collect::<Vec<(Point, Point)>>();
+            let segments = lines
+                .iter()
+                .map(|&(start, end)| Segment::new(start, end).unwrap())
+                .collect::<Vec<Segment>>();
+            let coverage = Coverage::new(&segments);
+            let counter = rasterized(&lines);
+            assert_eq!(coverage.histogram(), &counter.histogram(), "{:?}", lines);
+            assert_eq!(coverage.overlaps(2), counter.overlaps(2));
+            for (point, count) in counter.points() {
+                assert_eq!(coverage.at(point), count);
+            }
+        }
+    }
+
+    #[test]
+    fn merges_points_counted_apart() {
+        let segments = [
+            Segment::new((0, 0), (4, 0)).unwrap(),
+            Segment::new((2, -2), (2, 2)).unwrap(),
+        ];
+        let mut coverage = Coverage::new(&segments);
+        assert_eq!(
+            coverage.histogram().clone().into_iter().collect::<Vec<_>>(),
+            [(1, 8), (2, 1)]
+        );
+
+        // Points of lines of other slopes
+        let mut counter = Counter::for_bounds((0, -4), (4, 4));
+        for point in [(2, 0), (1, 2), (0, -4), (1, 2)] {
+            counter.add(point);
+        }
+        coverage.merge(&counter);
+        assert_eq!(coverage.at((2, 0)), 2);
+        assert_eq!(
+            coverage.histogram().clone().into_iter().collect::<Vec<_>>(),
+            [(1, 9), (2, 1), (3, 1)]
+        );
+        assert_eq!(coverage.overlaps(3), 1);
+    }
+
+    #[test]
+    fn rejects_other_slopes() {
+        assert_eq!(Segment::new((0, 0), (2, 1)), None);
+        let single = Segment::new((3, 4), (3, 4)).unwrap();
+        assert_eq!(
+            (single.family, single.line, single.low, single.high),
+            (Family::Horizontal, 4, 3, 3)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use aoc_common::{input_lines, Param, Params, ParseError, Rng, Solution};
+
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    let line = input_lines(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a list of numbers"))?;
+    line.text
+        .split(',')
+        .map(|x| {
+            let timer = line.parse::<i64>(x, "a timer")?;
+            if !(0..=8).contains(&timer) {
+                return Err(line.error(x, "a timer from 0 to 8"));
+            }
+            Ok(timer)
+        })
+        .collect::<Result<Vec<i64>, ParseError>>()
+}
+
+// First solution: dynamic programming
+// It is clearly not the best solution, but it was my original working solution
+// and took quite some time to nail, thus I'm keeping it here.
+// Complexity is O(n+d^2) where n is the input list size and d is the number of
+// days simulated. Space complexity is O(d).
+pub struct DynProg {
+    table: Vec<i64>,
+    n: i32,
+}
+
+impl DynProg {
+    pub fn new(n: usize) -> DynProg {
+        DynProg {
+            table: vec![-1; n],
+            n: n as i32,
+        }
+    }
+
+    pub fn calculate(&mut self, list: &[i64]) -> i64 {
+        list.iter()
+            .map(|&entry| self.calc_internal(self.n - entry as i32))
+            .sum()
+    }
+
+    fn calc_internal(&mut self, days: i32) -> i64 {
+        if days <= 0 {
+            return 1;
+        }
+        if self.table[days as usize] != -1 {
+            return self.table[days as usize];
+        }
+
+        let mut result = 1;
+
+        result += (0..)
+            .step_by(7)
+            .take_while(|i| days - i > 0)
+            .map(|i| self.calc_internal(days - i - 9))
+            .sum::<i64>();
+
+        self.table[days as usize] = result;
+        result
+    }
+}
+
+// The actual best solution using offspring cycle counters.
+// Complexity is O(n+d) where n is the input list size and
+// d is the number of days simulated. Returns `None` once the count overflows.
+pub fn counting_solution(start: &[i64], days: usize) -> Option<u128> {
+    let mut counter = [0u128; 9];
+
+    start.iter().for_each(|&x| counter[x as usize] += 1);
+
+    for _ in 0..days {
+        let number_offspring = counter[0];
+        (0..8).for_each(|i| counter[i] = counter[i + 1]);
+        counter[6] = counter[6].checked_add(number_offspring)?;
+        counter[8] = number_offspring;
+    }
+
+    counter.iter().try_fold(0u128, |sum, &c| sum.checked_add(c))
+}
+
+const PART1_DAYS: usize = 80;
+const PART2_DAYS: usize = 256;
+
+pub fn part1(start: &[i64], days: usize) -> Option<u128> {
+    counting_solution(start, days)
+}
+
+pub fn part2(start: &[i64], days: usize) -> Option<u128> {
+    counting_solution(start, days)
+}
+
+const OVERFLOW: &str = "the number of lanternfish overflows a u128";
+
+/// Timers of `size` lanternfish, between 1 and 5 like the puzzle's.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let timers = (0..size)
+        .map(|_| rng.range(1..=5).to_string())
+        .collect::<Vec<String>>();
+    format!("{}\n", timers.join(","))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    // Timers, and the number of days if given
+    type Input = (Vec<i64>, Option<usize>);
+    type Answer1 = Result<u128, &'static str>;
+    type Answer2 = Result<u128, &'static str>;
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "days",
+        help: "days to simulate (default: 80 for part 1, 256 for part 2)",
+        values: &[],
+    }];
+
+    const GENERATE_SIZE: usize = 300;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((parse_input(input)?, params.get("days")))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, input.1.unwrap_or(PART1_DAYS)).ok_or(OVERFLOW)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, input.1.unwrap_or(PART2_DAYS)).ok_or(OVERFLOW)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Answer;
+
+    #[test]
+    fn reports_overflow() {
+        let (timers, _) = Day6::parse("3,4,3,1,2\n").unwrap();
+        assert_eq!(part2(&timers, 256), Some(26984457539));
+        assert!(part2(&timers, 900).is_some());
+        assert_eq!(part2(&timers, 1100), None);
+        assert_eq!(
+            Answer::from(Day6::part2(&(timers, Some(2000)))).to_string(),
+            format!("none ({})", OVERFLOW)
+        );
+    }
+
+    #[test]
+    fn rejects_timers_out_of_range() {
+        let error = parse_input("3,9,1\n").err().unwrap();
+        assert_eq!((error.column, error.token.as_str()), (3, "9"));
+    }
+}
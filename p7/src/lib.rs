@@ -0,0 +1,88 @@
+use aoc_common::{input_lines, Params, ParseError, Rng, Solution};
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let line = input_lines(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a list of numbers"))?;
+    line.text
+        .split(',')
+        .map(|x| line.parse::<u32>(x, "a position"))
+        .collect::<Result<Vec<u32>, ParseError>>()
+}
+
+pub fn part1(list: &[u32]) -> u32 {
+    let mut positions = list.to_owned();
+    positions.sort_unstable();
+    let size = positions.len();
+    let median = if size.is_multiple_of(2) {
+        (positions[size / 2 - 1] + positions[size / 2]) / 2
+    } else {
+        positions[size / 2]
+    };
+
+    positions
+        .into_iter()
+        .map(|x| (median as i32 - x as i32).unsigned_abs())
+        .sum::<u32>()
+}
+
+#[inline]
+fn calculate_fuel(a: u32, b: u32) -> u32 {
+    let n = (a as i32 - b as i32).unsigned_abs();
+    ((1 + n) * n) / 2
+}
+
+pub fn part2(list: &[u32]) -> u32 {
+    let max_position = list.iter().max().unwrap();
+
+    (0..=*max_position)
+        .map(|position| {
+            list.iter()
+                .map(|&x| calculate_fuel(position, x))
+                .sum::<u32>()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Positions of `size` crabs, below 2000 like the puzzle's. Fewer positions
+/// are used for larger swarms, so that moving every crab anywhere still
+/// takes a number of fuel that fits in a u32.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max_position = (1..2000)
+        .take_while(|&p| p * (p + 1) / 2 * size <= u32::MAX as usize)
+        .last()
+        .unwrap_or(0);
+    let positions = (0..size)
+        .map(|_| rng.range(0..=max_position as i64).to_string())
+        .collect::<Vec<String>>();
+    format!("{}\n", positions.join(","))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const GENERATE_SIZE: usize = 1000;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
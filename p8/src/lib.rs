@@ -0,0 +1,193 @@
+use aoc_common::{input_lines, Line, Params, ParseError, Rng, Solution};
+use itertools::Itertools;
+
+/// Ten unique signal patterns and the four digits shown on the display
+pub type Entry = (Vec<String>, Vec<String>);
+
+fn parse_pattern(line: &Line, token: &str) -> Result<String, ParseError> {
+    match token
+        .char_indices()
+        .find(|&(_, c)| !('a'..='g').contains(&c))
+    {
+        Some((i, _)) => Err(line.error(&token[i..i + 1], "a segment between `a` and `g`")),
+        None => Ok(token.to_owned()),
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    input_lines(input)
+        .map(|line| {
+            let mut tokenizer = line.text.split_whitespace();
+            let signals = (0..10)
+                .map(|_| parse_pattern(&line, line.next(&mut tokenizer, "a signal pattern")?))
+                .collect::<Result<Vec<String>, ParseError>>()?;
+
+            line.literal(&mut tokenizer, "|")?;
+
+            let display = (0..4)
+                .map(|_| parse_pattern(&line, line.next(&mut tokenizer, "a display digit")?))
+                .collect::<Result<Vec<String>, ParseError>>()?;
+            line.finish(&mut tokenizer)?;
+
+            Ok((signals, display))
+        })
+        .collect::<Result<Vec<Entry>, ParseError>>()
+}
+
+pub fn part1(signals_display_list: &[Entry]) -> u32 {
+    let mut num_1 = 0;
+    let mut num_4 = 0;
+    let mut num_7 = 0;
+    let mut num_8 = 0;
+
+    for signals_display in signals_display_list {
+        let display = &signals_display.1;
+        for digit in display {
+            match digit.len() {
+                2 => num_1 += 1,
+                4 => num_4 += 1,
+                3 => num_7 += 1,
+                7 => num_8 += 1,
+                _ => {}
+            }
+        }
+    }
+
+    num_1 + num_4 + num_7 + num_8
+}
+
+fn letters_to_numeric(letters: &str) -> Result<usize, ()> {
+    match letters {
+        "abcefg" => Ok(0),
+        "cf" => Ok(1),
+        "acdeg" => Ok(2),
+        "acdfg" => Ok(3),
+        "bcdf" => Ok(4),
+        "abdfg" => Ok(5),
+        "abdefg" => Ok(6),
+        "acf" => Ok(7),
+        "abcdefg" => Ok(8),
+        "abcdfg" => Ok(9),
+        _ => Err(()),
+    }
+}
+
+pub fn part2(signals_display_list: &[Entry]) -> usize {
+    let alphabet = "abcdefg".chars().collect::<Vec<char>>();
+    let alphabet_len = alphabet.len();
+
+    signals_display_list
+        .iter()
+        .map(|signals_display| {
+            let signals = &signals_display.0;
+            for candidate in alphabet.iter().permutations(alphabet_len) {
+                let mut digits_found = vec![false; 10];
+                for signal in signals {
+                    let translated = signal
+                        .chars()
+                        .map(|c| candidate[c as usize - 'a' as usize])
+                        .sorted()
+                        .collect::<String>();
+                    let decoded_result = letters_to_numeric(&translated);
+                    let decoded = match decoded_result {
+                        Ok(x) => x,
+                        Err(_) => break,
+                    };
+                    if digits_found[decoded] {
+                        break;
+                    } else {
+                        digits_found[decoded] = true;
+                    }
+                }
+
+                if digits_found.into_iter().all(|x| x) {
+                    // Solved
+                    let display = &signals_display.1;
+                    let result = display
+                        .iter()
+                        .map(|component| {
+                            let translated = component
+                                .chars()
+                                .map(|c| candidate[c as usize - 'a' as usize])
+                                .sorted()
+                                .collect::<String>();
+                            let decoded_result = letters_to_numeric(&translated);
+                            let decoded = decoded_result.unwrap();
+                            decoded.to_string()
+                        })
+                        .collect::<String>();
+
+                    return result.parse::<usize>().unwrap();
+                }
+            }
+
+            panic!("No suitable mapping found");
+        })
+        .sum::<usize>()
+}
+
+// Segments lit for each digit, with the display wired correctly
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+fn scramble(rng: &mut Rng, wiring: &[char], digit: usize) -> String {
+    let mut pattern = DIGIT_SEGMENTS[digit]
+        .chars()
+        .map(|c| wiring[c as usize - 'a' as usize])
+        .collect::<Vec<char>>();
+    rng.shuffle(&mut pattern);
+    pattern.into_iter().collect()
+}
+
+/// `size` entries, each of a display with its own random wiring.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut result = String::new();
+    for _ in 0..size {
+        let mut wiring = "abcdefg".chars().collect::<Vec<char>>();
+        rng.shuffle(&mut wiring);
+        let mut digits = (0..10).collect::<Vec<usize>>();
+        rng.shuffle(&mut digits);
+
+        let signals = digits
+            .iter()
+            .map(|&digit| scramble(rng, &wiring, digit))
+            .collect::<Vec<String>>();
+        let display = (0..4)
+            .map(|_| {
+                let digit = rng.below(10);
+                scramble(rng, &wiring, digit)
+            })
+            .collect::<Vec<String>>();
+        result.push_str(&format!("{} | {}\n", signals.join(" "), display.join(" ")));
+    }
+    result
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = Vec<Entry>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    const GENERATE_SIZE: usize = 200;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}
@@ -0,0 +1,110 @@
+use aoc_common::{Grid, Params, ParseError, Rng, Solution};
+
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_digits(input)
+}
+
+mod common {
+    use aoc_common::{Grid, Pos};
+
+    pub fn is_low_point(grid: &Grid<u32>, pos: Pos) -> bool {
+        grid.neighbours4(pos).all(|n| grid[pos] < grid[n])
+    }
+
+    pub fn find_low_points(grid: &Grid<u32>) -> Vec<Pos> {
+        grid.positions()
+            .filter(|&pos| is_low_point(grid, pos))
+            .collect()
+    }
+}
+
+pub mod part1 {
+    use crate::common::find_low_points;
+    use aoc_common::Grid;
+
+    pub fn solve(grid: &Grid<u32>) -> u32 {
+        let low_points = find_low_points(grid);
+        low_points.into_iter().map(|pos| grid[pos] + 1).sum::<u32>()
+    }
+}
+
+pub mod part2 {
+    use crate::common::find_low_points;
+    use aoc_common::{Grid, Pos};
+
+    fn search(grid: &Grid<u32>, start: Pos) -> usize {
+        let mut visited = Grid::new(grid.width(), grid.height(), false);
+        let mut q = vec![start];
+        let mut basin_size = 0;
+
+        while let Some(pos) = q.pop() {
+            if grid[pos] == 9 || visited[pos] {
+                continue;
+            }
+
+            basin_size += 1;
+            visited[pos] = true;
+            q.extend(grid.neighbours4(pos));
+        }
+
+        basin_size
+    }
+
+    pub fn solve(grid: &Grid<u32>) -> usize {
+        let low_points = find_low_points(grid);
+
+        let mut result_vec = low_points
+            .into_iter()
+            .map(|point| search(grid, point))
+            .collect::<Vec<usize>>();
+
+        result_vec.sort_unstable();
+
+        result_vec.into_iter().rev().take(3).product::<usize>()
+    }
+}
+
+/// A random `size` x `size` heightmap. A quarter of the locations are 9s,
+/// which split it into basins.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    Grid::from_fn(
+        size,
+        size,
+        |_| {
+            if rng.chance(1, 4) {
+                9
+            } else {
+                rng.below(9)
+            }
+        },
+    )
+    .to_string()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Input = Grid<u32>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    const GENERATE_SIZE: usize = 100;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::solve(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> String {
+        generate(rng, size)
+    }
+}